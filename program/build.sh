@@ -3,6 +3,8 @@ set -v -e
 docker build -t solana_build .
 
 solana program dump namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX target/deploy/spl_name_service.so
+# The deployed records program, the baseline of the compute unit benchmarks
+solana program dump HP3D4D1ZCmohQGFVms2SS4LCANgJyksBf5s1F77FuFjZ target/deploy/sns_records_baseline.so
 
 docker run -it --mount type=bind,source=$(pwd),target=/workdir --env CARGO_REGISTRIES_CRATES_IO_PROTOCOL=sparse \
 --env SSH_AUTH_SOCK=/ssh-agent \
@@ -11,4 +13,8 @@ solana_build:latest cargo test-sbf
 docker run -it --mount type=bind,source=$(pwd),target=/workdir --env CARGO_REGISTRIES_CRATES_IO_PROTOCOL=sparse \
 --env SSH_AUTH_SOCK=/ssh-agent \
 --mount type=bind,source=$SSH_AUTH_SOCK,target=/ssh-agent \
+solana_build:latest cargo test-sbf --features test-bpf --test compute_units -- --nocapture
+docker run -it --mount type=bind,source=$(pwd),target=/workdir --env CARGO_REGISTRIES_CRATES_IO_PROTOCOL=sparse \
+--env SSH_AUTH_SOCK=/ssh-agent \
+--mount type=bind,source=$SSH_AUTH_SOCK,target=/ssh-agent \
 solana_build:latest cargo build-sbf
\ No newline at end of file
@@ -0,0 +1,1050 @@
+//! Offline tooling to inspect and craft SNS records
+
+use std::{fs, path::PathBuf, str::FromStr};
+
+use {
+    base64::{
+        engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+        Engine,
+    },
+    clap::{Parser, Subcommand},
+    serde_json::{json, Value},
+    solana_program::{
+        hash::Hash, instruction::Instruction, message::Message, program_pack::Pack, pubkey::Pubkey,
+        system_program,
+    },
+    spl_associated_token_account::get_associated_token_address,
+    spl_name_service::state::{get_seeds_and_key, NameRecordHeader},
+};
+
+use sns_records::{
+    idl,
+    instruction::{
+        self,
+        validate_ethereum_signature::{message_to_sign, record_message},
+        validate_secp256r1_signature::webauthn_challenge,
+    },
+    record_kind::RecordKind,
+    state::{
+        config::Config,
+        content_encoding::{self, ContentEncoding},
+        off_chain::OffChainPointer,
+        parent_authority::ParentAuthority,
+        record::DecodedRecord,
+        record_list::{self, RecordEntry},
+        validation::Validation,
+    },
+    utils::{get_domain_mint, get_hashed_name, get_record_key_and_seeds, ROOT_DOMAIN},
+};
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[clap(
+    name = "sns-records",
+    version,
+    about = "Inspect and craft SNS records offline"
+)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Derive the key of a record account
+    RecordKey {
+        /// The domain, either as a public key or as a name (e.g `bonfida.sol`)
+        domain: String,
+        /// The record name (e.g `SOL`)
+        record: String,
+    },
+    /// Decode a record account data file into JSON
+    Decode {
+        /// Path of the raw account data
+        file: PathBuf,
+        /// The file holds base64 encoded data instead of raw bytes
+        #[clap(long)]
+        base64: bool,
+        /// The key of the record account, used to label well-known records
+        #[clap(long)]
+        key: Option<Pubkey>,
+    },
+    /// Check a downloaded off-chain payload against a record account data file
+    VerifyPayload {
+        /// Path of the raw account data
+        file: PathBuf,
+        /// The file holds base64 encoded data instead of raw bytes
+        #[clap(long)]
+        base64: bool,
+        /// Path of the downloaded payload
+        payload: PathBuf,
+    },
+    /// Encode a value into the content of a record
+    Encode {
+        /// The record name (e.g `SOL`)
+        record: String,
+        /// The human readable value
+        value: String,
+    },
+    /// Print the message an ETH wallet must sign to validate a RoA
+    Message {
+        /// The record account
+        record_key: Pubkey,
+        /// The current staleness id of the record
+        staleness_id: Pubkey,
+        #[clap(flatten)]
+        content: ContentArgs,
+        /// Print the base64url WebAuthn challenge for a passkey instead
+        #[clap(long)]
+        webauthn: bool,
+        /// Print the data to pass to a Cosmos wallet `signArbitrary` instead
+        #[clap(long, conflicts_with = "webauthn")]
+        cosmos: bool,
+    },
+    /// Emit a serialized unsigned transaction (base64) for an instruction
+    Tx {
+        #[clap(subcommand)]
+        instruction: TxInstruction,
+        /// The recent blockhash to use, zeroes by default
+        #[clap(long, global = true)]
+        blockhash: Option<String>,
+        /// Sign as the holder of the domain NFT, for domains tokenized by the name tokenizer
+        #[clap(long, global = true)]
+        token_gated: bool,
+        /// Sign through these signers of a multisig domain owner, the fee payer must be set
+        #[clap(long, global = true, use_value_delimiter = true)]
+        multisig_signers: Vec<Pubkey>,
+    },
+    /// Print the Shank compatible IDL of the program
+    Idl {
+        /// Fail if the IDL differs from the one checked in at this path
+        #[clap(long)]
+        check: Option<PathBuf>,
+    },
+}
+
+#[derive(clap::Args)]
+struct ContentArgs {
+    /// The record content as UTF-8
+    #[clap(long, conflicts_with = "content-hex")]
+    content: Option<String>,
+    /// The record content as hex
+    #[clap(long)]
+    content_hex: Option<String>,
+    /// Store the content compressed with DEFLATE
+    #[clap(long, conflicts_with_all = &["off-chain", "list"])]
+    deflate: bool,
+    /// Store a pointer to the content instead (`ipfs://<cid>`, `ar://<tx id>` or an HTTPS URL),
+    /// the content is the off-chain payload
+    #[clap(long, conflicts_with = "list")]
+    off_chain: Option<String>,
+    /// Store the content as a list record holding it as its first entry
+    #[clap(long)]
+    list: bool,
+}
+
+impl ContentArgs {
+    fn bytes(&self) -> CliResult<Vec<u8>> {
+        match (&self.content, &self.content_hex) {
+            (Some(c), None) => Ok(c.as_bytes().to_vec()),
+            (None, Some(h)) => Ok(hex::decode(h.trim_start_matches("0x"))?),
+            _ => Err("Either --content or --content-hex must be provided".into()),
+        }
+    }
+
+    /// The content as it must be stored, along with its encoding
+    fn encoded(&self) -> CliResult<(Vec<u8>, ContentEncoding)> {
+        if let Some(uri) = &self.off_chain {
+            let pointer = OffChainPointer::from_uri(uri, &self.bytes()?)?;
+            return Ok((pointer.to_bytes(), ContentEncoding::OffChain));
+        }
+        if self.list {
+            let entries = [RecordEntry::new(self.bytes()?)];
+            return Ok((
+                record_list::serialize_entries(&entries)?,
+                ContentEncoding::List,
+            ));
+        }
+        let encoding = if self.deflate {
+            ContentEncoding::Deflate
+        } else {
+            ContentEncoding::None
+        };
+        Ok((content_encoding::encode(encoding, &self.bytes()?), encoding))
+    }
+
+    /// The content validation messages commit to: the payload hash for off-chain content, the
+    /// serialized entries of a list and the decoded content otherwise
+    fn committed(&self) -> CliResult<Vec<u8>> {
+        if self.off_chain.is_some() {
+            return Ok(solana_program::hash::hash(&self.bytes()?)
+                .to_bytes()
+                .to_vec());
+        }
+        if self.list {
+            return Ok(self.encoded()?.0);
+        }
+        self.bytes()
+    }
+}
+
+#[derive(clap::Args)]
+struct RecordArgs {
+    /// The domain, either as a public key or as a name (e.g `bonfida.sol`)
+    #[clap(long)]
+    domain: String,
+    /// The record name (e.g `SOL`)
+    #[clap(long)]
+    record: String,
+    /// The fee payer, defaults to the domain owner
+    #[clap(long)]
+    fee_payer: Option<Pubkey>,
+}
+
+#[derive(clap::Args)]
+struct ParentArgs {
+    /// Sign as the owner of this parent domain, the subdomain must have opted in
+    #[clap(long)]
+    parent_domain: Option<String>,
+}
+
+impl ParentArgs {
+    /// The parent domain and the parent authority account of `domain`, in parent authority mode
+    fn resolve(&self, domain: &Pubkey) -> CliResult<Option<(Pubkey, Pubkey)>> {
+        self.parent_domain
+            .as_ref()
+            .map(|parent| {
+                let (parent_authority, _) = ParentAuthority::find_key(domain, &sns_records::ID);
+                Ok((parse_domain(parent)?, parent_authority))
+            })
+            .transpose()
+    }
+}
+
+#[derive(Subcommand)]
+enum TxInstruction {
+    AllocateRecord {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        #[clap(long)]
+        content_length: u32,
+    },
+    AllocateAndPostRecord {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        #[clap(flatten)]
+        content: ContentArgs,
+    },
+    EditRecord {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        #[clap(flatten)]
+        content: ContentArgs,
+        #[clap(flatten)]
+        parent: ParentArgs,
+    },
+    ValidateSolanaSignature {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        #[clap(long)]
+        verifier: Pubkey,
+        /// Validate the staleness instead of the RoA
+        #[clap(long)]
+        staleness: bool,
+    },
+    ValidateEthereumSignature {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        /// The 65 bytes signature as hex
+        #[clap(long)]
+        signature: String,
+        /// The expected ETH address as hex
+        #[clap(long)]
+        expected_pubkey: String,
+    },
+    ValidateCosmosSignature {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        /// The bech32 address of the signer
+        #[clap(long)]
+        address: String,
+        /// The 64 bytes signature as hex
+        #[clap(long)]
+        signature: String,
+    },
+    DeleteRecord {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+    },
+    WriteRoa {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        /// The RoA id to write
+        #[clap(long)]
+        roa_id: Pubkey,
+    },
+    UnverifyRoa {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        verifier: Pubkey,
+    },
+    AddRecordEntry {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        /// The human readable value of the entry, encoded like the `encode` command does
+        #[clap(long)]
+        entry: String,
+        #[clap(flatten)]
+        parent: ParentArgs,
+    },
+    RemoveRecordEntry {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        #[clap(long)]
+        index: u16,
+        #[clap(flatten)]
+        parent: ParentArgs,
+    },
+    /// Let the owner of the parent domain manage the records of a subdomain
+    SetParentAuthority {
+        /// The subdomain public key
+        #[clap(long)]
+        domain: Pubkey,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        /// The fee payer, defaults to the domain owner
+        #[clap(long)]
+        fee_payer: Option<Pubkey>,
+        /// Opt out instead
+        #[clap(long)]
+        disable: bool,
+    },
+    /// Re-stamp the staleness of the given records after a domain transfer
+    RefreshStaleness {
+        /// The domain, either as a public key or as a name (e.g `bonfida.sol`)
+        #[clap(long)]
+        domain: String,
+        /// The new domain owner
+        #[clap(long)]
+        domain_owner: Pubkey,
+        /// The fee payer, defaults to the domain owner
+        #[clap(long)]
+        fee_payer: Option<Pubkey>,
+        /// The record names (e.g `SOL,ETH`)
+        #[clap(long, use_value_delimiter = true, required = true)]
+        records: Vec<String>,
+    },
+    ValidateEntryEthereumSignature {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        #[clap(long)]
+        index: u16,
+        /// The 65 bytes signature of the entry message as hex
+        #[clap(long)]
+        signature: String,
+    },
+    UnverifyEntry {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        verifier: Pubkey,
+        #[clap(long)]
+        index: u16,
+    },
+    ValidateEntrySolanaSignature {
+        #[clap(flatten)]
+        record: RecordArgs,
+        #[clap(long)]
+        domain_owner: Pubkey,
+        /// The entry address, which must sign
+        #[clap(long)]
+        verifier: Pubkey,
+        #[clap(long)]
+        index: u16,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run(command: Command) -> CliResult<String> {
+    match command {
+        Command::RecordKey { domain, record } => {
+            let domain = parse_domain(&domain)?;
+            let (key, seeds) = get_record_key_and_seeds(&domain, &record);
+            let output = json!({
+                "domain": domain.to_string(),
+                "record": record,
+                "recordKey": key.to_string(),
+                "hashedName": hex::encode(get_hashed_name(&record)),
+                "seeds": hex::encode(seeds),
+            });
+            Ok(serde_json::to_string_pretty(&output)?)
+        }
+        Command::Decode { file, base64, key } => {
+            let data = read_account_data(file, base64)?;
+            Ok(serde_json::to_string_pretty(&decode(&data, key)?)?)
+        }
+        Command::VerifyPayload {
+            file,
+            base64,
+            payload,
+        } => {
+            let record = DecodedRecord::from_account_data(&read_account_data(file, base64)?)?;
+            let pointer = record
+                .off_chain_pointer()?
+                .ok_or("The record content is not stored off-chain")?;
+            pointer.verify_payload(&fs::read(payload)?)?;
+            Ok(format!("The payload matches {}", pointer.uri()))
+        }
+        Command::Encode { record, value } => {
+            let kind = RecordKind::from_str(&record).ok();
+            Ok(hex::encode(encode_content(kind, &value)?))
+        }
+        Command::Message {
+            record_key,
+            staleness_id,
+            content,
+            webauthn,
+            cosmos,
+        } => {
+            if cosmos {
+                let message = record_message(&content.committed()?, &record_key, &staleness_id);
+                return Ok(String::from_utf8_lossy(&message).into_owned());
+            }
+            if webauthn {
+                let challenge =
+                    webauthn_challenge(&content.committed()?, &record_key, &staleness_id);
+                return Ok(URL_SAFE_NO_PAD.encode(challenge));
+            }
+            let message = message_to_sign(&content.committed()?, &record_key, &staleness_id);
+            Ok(String::from_utf8_lossy(&message).into_owned())
+        }
+        Command::Idl { check } => {
+            let idl = serde_json::to_string_pretty(&idl::generate())?;
+            if let Some(path) = check {
+                let checked_in: Value = serde_json::from_slice(&fs::read(&path)?)?;
+                if checked_in != idl::generate() {
+                    return Err(format!("{} is out of date", path.display()).into());
+                }
+            }
+            Ok(idl)
+        }
+        Command::Tx {
+            instruction,
+            blockhash,
+            token_gated,
+            multisig_signers,
+        } => {
+            let blockhash = match blockhash {
+                Some(b) => Hash::from_str(&b)?,
+                None => Hash::default(),
+            };
+            let (instruction, fee_payer) =
+                build_instruction(instruction, token_gated, &multisig_signers)?;
+            Ok(STANDARD.encode(unsigned_transaction(instruction, &fee_payer, blockhash)))
+        }
+    }
+}
+
+/// Parses a domain public key or derives it from a `.sol` name
+fn parse_domain(domain: &str) -> CliResult<Pubkey> {
+    if let Ok(key) = Pubkey::from_str(domain) {
+        return Ok(key);
+    }
+    let name = domain.strip_suffix(".sol").unwrap_or(domain);
+    if name.contains('.') {
+        return Err("Subdomains must be passed as a public key".into());
+    }
+    let (key, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        get_hashed_name(name),
+        None,
+        Some(&ROOT_DOMAIN),
+    );
+    Ok(key)
+}
+
+fn read_account_data(file: PathBuf, base64: bool) -> CliResult<Vec<u8>> {
+    let data = fs::read(file)?;
+    if base64 {
+        return Ok(STANDARD.decode(String::from_utf8(data)?.trim())?);
+    }
+    Ok(data)
+}
+
+fn decode(data: &[u8], key: Option<Pubkey>) -> CliResult<Value> {
+    let name_header = NameRecordHeader::unpack_from_slice(
+        data.get(..NameRecordHeader::LEN)
+            .ok_or("The account is too small to be a name account")?,
+    )?;
+    let record = DecodedRecord::from_account_data(data)?;
+    let off_chain = record.off_chain_pointer()?.map(|p| {
+        json!({
+            "uri": p.uri(),
+            "hash": hex::encode(p.hash),
+        })
+    });
+    let entries = record.entries()?;
+    let mut output = json!({
+        "owner": name_header.owner.to_string(),
+        "parentName": name_header.parent_name.to_string(),
+        "class": name_header.class.to_string(),
+        "kind": key
+            .and_then(|k| RecordKind::from_record_key(&name_header.parent_name, &k))
+            .map(|k| k.as_str()),
+        "offChain": off_chain,
+        "entries": entries,
+    });
+    if let (Value::Object(output), Value::Object(record)) =
+        (&mut output, serde_json::to_value(record)?)
+    {
+        output.extend(record);
+    }
+    Ok(output)
+}
+
+fn encode_content(kind: Option<RecordKind>, value: &str) -> CliResult<Vec<u8>> {
+    let content = match kind {
+        Some(RecordKind::Sol) => Pubkey::from_str(value)?.to_bytes().to_vec(),
+        Some(RecordKind::Eth) | Some(RecordKind::Bsc) | Some(RecordKind::Base) => {
+            let address = hex::decode(value.trim_start_matches("0x"))?;
+            if address.len() != 20 {
+                return Err("ETH addresses must be 20 bytes long".into());
+            }
+            address
+        }
+        Some(RecordKind::A) => std::net::Ipv4Addr::from_str(value)?.octets().to_vec(),
+        Some(RecordKind::Aaaa) => std::net::Ipv6Addr::from_str(value)?.octets().to_vec(),
+        _ => value.as_bytes().to_vec(),
+    };
+    Ok(content)
+}
+
+fn build_instruction(
+    instruction: TxInstruction,
+    token_gated: bool,
+    multisig_signers: &[Pubkey],
+) -> CliResult<(Instruction, Pubkey)> {
+    use sns_records::instruction::{
+        add_record_entry, allocate_and_post_record, allocate_record, delete_record, edit_record,
+        refresh_staleness, remove_record_entry, set_parent_authority, unverify_entry, unverify_roa,
+        validate_cosmos_signature, validate_entry_ethereum_signature,
+        validate_entry_solana_signature, validate_ethereum_signature, validate_solana_signature,
+        write_roa,
+    };
+
+    let central_state = sns_records::central_state::KEY;
+    let (config, _) = Config::find_key(&sns_records::ID);
+    let resolve = |args: &RecordArgs, signer: &Pubkey| -> CliResult<_> {
+        let domain = parse_domain(&args.domain)?;
+        let (record, _) = get_record_key_and_seeds(&domain, &args.record);
+        Ok((domain, record, args.fee_payer.unwrap_or(*signer)))
+    };
+    // The associated token account holding the domain NFT, in token-gated mode
+    let token_account = |domain: &Pubkey, holder: &Pubkey| {
+        token_gated.then(|| get_associated_token_address(holder, &get_domain_mint(domain)))
+    };
+
+    let result = match instruction {
+        TxInstruction::AllocateRecord {
+            record: args,
+            domain_owner,
+            content_length,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let ix = instruction::allocate_record(
+                allocate_record::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                },
+                allocate_record::Params {
+                    content_length,
+                    record: args.record,
+                },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::AllocateAndPostRecord {
+            record: args,
+            domain_owner,
+            content,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let (content, content_encoding) = content.encoded()?;
+            let ix = instruction::allocate_and_post_record(
+                allocate_and_post_record::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                },
+                allocate_and_post_record::Params {
+                    record: args.record,
+                    content,
+                    content_encoding,
+                },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::EditRecord {
+            record: args,
+            domain_owner,
+            content,
+            parent,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let parent = parent.resolve(&domain)?;
+            let (content, content_encoding) = content.encoded()?;
+            let domain_token_account = token_account(
+                parent
+                    .as_ref()
+                    .map_or(&domain, |(parent_domain, _)| parent_domain),
+                &domain_owner,
+            );
+            let ix = instruction::edit_record(
+                edit_record::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    parent_domain: parent.as_ref().map(|(parent_domain, _)| parent_domain),
+                    parent_authority: parent
+                        .as_ref()
+                        .map(|(_, parent_authority)| parent_authority),
+                    multisig_signers,
+                },
+                edit_record::Params {
+                    record: args.record,
+                    content,
+                    content_encoding,
+                },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::ValidateSolanaSignature {
+            record: args,
+            domain_owner,
+            verifier,
+            staleness,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &verifier)?;
+            let domain_token_account = token_account(&domain, &domain_owner);
+            let ix = instruction::validate_solana_signature(
+                validate_solana_signature::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    verifier: &verifier,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    multisig_signers,
+                },
+                validate_solana_signature::Params { staleness },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::ValidateEthereumSignature {
+            record: args,
+            domain_owner,
+            signature,
+            expected_pubkey,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let domain_token_account = token_account(&domain, &domain_owner);
+            let ix = instruction::validate_ethereum_signature(
+                validate_ethereum_signature::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    multisig_signers,
+                },
+                validate_ethereum_signature::Params {
+                    validation: Validation::Ethereum,
+                    signature: hex::decode(signature.trim_start_matches("0x"))?,
+                    expected_pubkey: hex::decode(expected_pubkey.trim_start_matches("0x"))?,
+                },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::ValidateCosmosSignature {
+            record: args,
+            domain_owner,
+            address,
+            signature,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let domain_token_account = token_account(&domain, &domain_owner);
+            let ix = instruction::validate_cosmos_signature(
+                validate_cosmos_signature::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    multisig_signers,
+                },
+                validate_cosmos_signature::Params {
+                    address,
+                    signature: hex::decode(signature.trim_start_matches("0x"))?,
+                },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::DeleteRecord {
+            record: args,
+            domain_owner,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let domain_token_account = token_account(&domain, &domain_owner);
+            let ix = instruction::delete_record(
+                delete_record::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    multisig_signers,
+                },
+                delete_record::Params {},
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::WriteRoa {
+            record: args,
+            domain_owner,
+            roa_id,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let domain_token_account = token_account(&domain, &domain_owner);
+            let ix = instruction::write_roa(
+                write_roa::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    multisig_signers,
+                },
+                write_roa::Params {
+                    roa_id: roa_id.to_bytes().to_vec(),
+                },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::UnverifyRoa {
+            record: args,
+            verifier,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &verifier)?;
+            let ix = instruction::unverify_roa(
+                unverify_roa::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    central_state: &central_state,
+                    verifier: &verifier,
+                    config: &config,
+                },
+                unverify_roa::Params {},
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::AddRecordEntry {
+            record: args,
+            domain_owner,
+            entry,
+            parent,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let parent = parent.resolve(&domain)?;
+            let kind = RecordKind::from_str(&args.record).ok();
+            let domain_token_account = token_account(
+                parent
+                    .as_ref()
+                    .map_or(&domain, |(parent_domain, _)| parent_domain),
+                &domain_owner,
+            );
+            let ix = instruction::add_record_entry(
+                add_record_entry::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    parent_domain: parent.as_ref().map(|(parent_domain, _)| parent_domain),
+                    parent_authority: parent
+                        .as_ref()
+                        .map(|(_, parent_authority)| parent_authority),
+                    multisig_signers,
+                },
+                add_record_entry::Params {
+                    entry: encode_content(kind, &entry)?,
+                    record: args.record,
+                },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::RemoveRecordEntry {
+            record: args,
+            domain_owner,
+            index,
+            parent,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let parent = parent.resolve(&domain)?;
+            let domain_token_account = token_account(
+                parent
+                    .as_ref()
+                    .map_or(&domain, |(parent_domain, _)| parent_domain),
+                &domain_owner,
+            );
+            let ix = instruction::remove_record_entry(
+                remove_record_entry::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    parent_domain: parent.as_ref().map(|(parent_domain, _)| parent_domain),
+                    parent_authority: parent
+                        .as_ref()
+                        .map(|(_, parent_authority)| parent_authority),
+                    multisig_signers,
+                },
+                remove_record_entry::Params { index },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::ValidateEntrySolanaSignature {
+            record: args,
+            domain_owner,
+            verifier,
+            index,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &verifier)?;
+            let ix = instruction::validate_entry_solana_signature(
+                validate_entry_solana_signature::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    verifier: &verifier,
+                    config: &config,
+                },
+                validate_entry_solana_signature::Params { index },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::ValidateEntryEthereumSignature {
+            record: args,
+            domain_owner,
+            index,
+            signature,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &domain_owner)?;
+            let domain_token_account = token_account(&domain, &domain_owner);
+            let ix = instruction::validate_entry_ethereum_signature(
+                validate_entry_ethereum_signature::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    multisig_signers,
+                },
+                validate_entry_ethereum_signature::Params {
+                    index,
+                    signature: hex::decode(signature.trim_start_matches("0x"))?,
+                },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::RefreshStaleness {
+            domain,
+            domain_owner,
+            fee_payer,
+            records,
+        } => {
+            let domain = parse_domain(&domain)?;
+            let fee_payer = fee_payer.unwrap_or(domain_owner);
+            let records = records
+                .iter()
+                .map(|r| get_record_key_and_seeds(&domain, r).0)
+                .collect::<Vec<_>>();
+            let domain_token_account = token_account(&domain, &domain_owner);
+            let ix = instruction::refresh_staleness(
+                refresh_staleness::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    central_state: &central_state,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    records: &records,
+                    multisig_signers,
+                },
+                refresh_staleness::Params {},
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::SetParentAuthority {
+            domain,
+            domain_owner,
+            fee_payer,
+            disable,
+        } => {
+            let fee_payer = fee_payer.unwrap_or(domain_owner);
+            let (parent_authority, _) = ParentAuthority::find_key(&domain, &sns_records::ID);
+            let domain_token_account = token_account(&domain, &domain_owner);
+            let ix = instruction::set_parent_authority(
+                set_parent_authority::Accounts {
+                    system_program: &system_program::ID,
+                    fee_payer: &fee_payer,
+                    domain: &domain,
+                    domain_owner: &domain_owner,
+                    parent_authority: &parent_authority,
+                    config: &config,
+                    domain_token_account: domain_token_account.as_ref(),
+                    multisig_signers,
+                },
+                set_parent_authority::Params { enabled: !disable },
+            );
+            (ix, fee_payer)
+        }
+        TxInstruction::UnverifyEntry {
+            record: args,
+            verifier,
+            index,
+        } => {
+            let (domain, record, fee_payer) = resolve(&args, &verifier)?;
+            let ix = instruction::unverify_entry(
+                unverify_entry::Accounts {
+                    system_program: &system_program::ID,
+                    spl_name_service_program: &spl_name_service::ID,
+                    fee_payer: &fee_payer,
+                    record: &record,
+                    domain: &domain,
+                    central_state: &central_state,
+                    verifier: &verifier,
+                    config: &config,
+                },
+                unverify_entry::Params { index },
+            );
+            (ix, fee_payer)
+        }
+    };
+    Ok(result)
+}
+
+/// Serializes a transaction with zeroed signatures, in the wire format expected by wallets
+fn unsigned_transaction(instruction: Instruction, fee_payer: &Pubkey, blockhash: Hash) -> Vec<u8> {
+    let message = Message::new_with_blockhash(&[instruction], Some(fee_payer), &blockhash);
+    let signatures = message.header.num_required_signatures as u16;
+
+    let mut transaction = Vec::new();
+    // Compact u16 encoding of the number of signatures
+    let mut rem = signatures;
+    loop {
+        let mut elem = (rem & 0x7f) as u8;
+        rem >>= 7;
+        if rem != 0 {
+            elem |= 0x80;
+        }
+        transaction.push(elem);
+        if rem == 0 {
+            break;
+        }
+    }
+    transaction.resize(transaction.len() + 64 * signatures as usize, 0);
+    transaction.extend_from_slice(&message.serialize());
+    transaction
+}
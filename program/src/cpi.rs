@@ -1,9 +1,16 @@
+use std::convert::TryInto;
+
+/// Helpers for other programs to invoke the records program
+pub mod records;
+
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed,
-    program_pack::Pack, rent::Rent, sysvar::Sysvar,
+    program_pack::Pack, rent::Rent, system_instruction, sysvar::Sysvar,
 };
 use spl_name_service::state::NameRecordHeader;
 
+use crate::{error::SnsRecordsError, state::transition::changed_range};
+
 #[allow(clippy::too_many_arguments)]
 pub fn allocate_record<'a>(
     space: u32,
@@ -15,9 +22,11 @@ pub fn allocate_record<'a>(
     central_state: &AccountInfo<'a>,
     system_program: &AccountInfo<'a>,
 ) -> ProgramResult {
-    let lamports = Rent::get()
-        .unwrap()
-        .minimum_balance(space as usize + NameRecordHeader::LEN);
+    let lamports = Rent::get().unwrap().minimum_balance(
+        (space as usize)
+            .checked_add(NameRecordHeader::LEN)
+            .ok_or(SnsRecordsError::NumericalOverflow)?,
+    );
 
     let ix = spl_name_service::instruction::create(
         spl_name_service::ID,
@@ -50,7 +59,7 @@ pub fn allocate_record<'a>(
 }
 
 pub fn edit_record<'a>(
-    data: &[u8],
+    data: Vec<u8>,
     offset: u32,
     record: &AccountInfo<'a>,
     central_state: &AccountInfo<'a>,
@@ -58,7 +67,7 @@ pub fn edit_record<'a>(
     let ix = spl_name_service::instruction::update(
         spl_name_service::ID,
         offset,
-        data.to_vec(),
+        data,
         *record.key,
         crate::central_state::KEY,
         None,
@@ -116,3 +125,74 @@ pub fn resize_record<'a>(
         &[&crate::central_state::SIGNER_SEEDS],
     )
 }
+
+/// Writes the serialized record `data` after the `NameRecordHeader`, resizing the account if needed
+///
+/// Only the bytes that changed are sent to the name service, and no CPI is made at all when the
+/// record is left untouched.
+pub fn write_record<'a>(
+    data: &[u8],
+    record: &AccountInfo<'a>,
+    central_state: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let (range, current_length) = {
+        let current = record.data.borrow();
+        let current = current
+            .get(NameRecordHeader::LEN..)
+            .ok_or(SnsRecordsError::OutOfBound)?;
+        (changed_range(current, data), current.len())
+    };
+
+    let range = match range {
+        Some(range) => range,
+        None => return Ok(()),
+    };
+
+    if data.len() != current_length {
+        resize_record(
+            record,
+            central_state,
+            fee_payer,
+            system_program,
+            data.len()
+                .try_into()
+                .map_err(|_| SnsRecordsError::NumericalOverflow)?,
+        )?;
+    }
+
+    if range.is_empty() {
+        // The record was only truncated
+        return Ok(());
+    }
+
+    let offset = range
+        .start
+        .try_into()
+        .map_err(|_| SnsRecordsError::NumericalOverflow)?;
+    edit_record(data[range].to_vec(), offset, record, central_state)
+}
+
+/// Creates an account owned by this program at the PDA derived from `seeds`
+pub fn create_program_account<'a>(
+    space: usize,
+    seeds: &[&[u8]],
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(space);
+    let ix = system_instruction::create_account(
+        payer.key,
+        account.key,
+        lamports,
+        space as u64,
+        &crate::ID,
+    );
+    invoke_signed(
+        &ix,
+        &[system_program.clone(), payer.clone(), account.clone()],
+        &[seeds],
+    )
+}
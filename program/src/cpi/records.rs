@@ -0,0 +1,231 @@
+//! Helpers for other programs to invoke the records program
+//!
+//! Each helper takes the `Accounts` struct of the instruction with the account infos received by
+//! the caller. `signer_seeds` lets a PDA sign, for instance a domain owned by the caller program,
+//! and is left empty otherwise. When the domain owner is an SPL Token multisig, its signers go in
+//! `multisig_signers` and the multisig itself is not marked as a signer.
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed, pubkey::Pubkey,
+};
+
+use crate::instruction::{
+    self, allocate_and_post_record, allocate_record, delete_record, edit_record,
+    validate_solana_signature, write_roa,
+};
+
+fn keys(accounts: &[AccountInfo]) -> Vec<Pubkey> {
+    accounts.iter().map(|a| *a.key).collect()
+}
+
+pub fn invoke_allocate_record<'a>(
+    accounts: allocate_record::Accounts<AccountInfo<'a>>,
+    params: allocate_record::Params,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::allocate_record(
+        allocate_record::Accounts {
+            system_program: accounts.system_program.key,
+            spl_name_service_program: accounts.spl_name_service_program.key,
+            fee_payer: accounts.fee_payer.key,
+            record: accounts.record.key,
+            domain: accounts.domain.key,
+            domain_owner: accounts.domain_owner.key,
+            central_state: accounts.central_state.key,
+            config: accounts.config.key,
+        },
+        params,
+    );
+    let account_infos = vec![
+        accounts.system_program.clone(),
+        accounts.spl_name_service_program.clone(),
+        accounts.fee_payer.clone(),
+        accounts.record.clone(),
+        accounts.domain.clone(),
+        accounts.domain_owner.clone(),
+        accounts.central_state.clone(),
+        accounts.config.clone(),
+    ];
+    invoke_signed(&ix, &account_infos, signer_seeds)
+}
+
+pub fn invoke_allocate_and_post_record<'a>(
+    accounts: allocate_and_post_record::Accounts<AccountInfo<'a>>,
+    params: allocate_and_post_record::Params,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: accounts.system_program.key,
+            spl_name_service_program: accounts.spl_name_service_program.key,
+            fee_payer: accounts.fee_payer.key,
+            record: accounts.record.key,
+            domain: accounts.domain.key,
+            domain_owner: accounts.domain_owner.key,
+            central_state: accounts.central_state.key,
+            config: accounts.config.key,
+        },
+        params,
+    );
+    let account_infos = vec![
+        accounts.system_program.clone(),
+        accounts.spl_name_service_program.clone(),
+        accounts.fee_payer.clone(),
+        accounts.record.clone(),
+        accounts.domain.clone(),
+        accounts.domain_owner.clone(),
+        accounts.central_state.clone(),
+        accounts.config.clone(),
+    ];
+    invoke_signed(&ix, &account_infos, signer_seeds)
+}
+
+pub fn invoke_edit_record<'a>(
+    accounts: edit_record::Accounts<AccountInfo<'a>>,
+    params: edit_record::Params,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let multisig_signers = keys(accounts.multisig_signers);
+    let ix = instruction::edit_record(
+        edit_record::Accounts {
+            system_program: accounts.system_program.key,
+            spl_name_service_program: accounts.spl_name_service_program.key,
+            fee_payer: accounts.fee_payer.key,
+            record: accounts.record.key,
+            domain: accounts.domain.key,
+            domain_owner: accounts.domain_owner.key,
+            central_state: accounts.central_state.key,
+            config: accounts.config.key,
+            domain_token_account: accounts.domain_token_account.map(|a| a.key),
+            parent_domain: accounts.parent_domain.map(|a| a.key),
+            parent_authority: accounts.parent_authority.map(|a| a.key),
+            multisig_signers: &multisig_signers,
+        },
+        params,
+    );
+    let mut account_infos = vec![
+        accounts.system_program.clone(),
+        accounts.spl_name_service_program.clone(),
+        accounts.fee_payer.clone(),
+        accounts.record.clone(),
+        accounts.domain.clone(),
+        accounts.domain_owner.clone(),
+        accounts.central_state.clone(),
+        accounts.config.clone(),
+    ];
+    account_infos.extend(accounts.domain_token_account.cloned());
+    account_infos.extend(accounts.parent_domain.cloned());
+    account_infos.extend(accounts.parent_authority.cloned());
+    account_infos.extend_from_slice(accounts.multisig_signers);
+    invoke_signed(&ix, &account_infos, signer_seeds)
+}
+
+pub fn invoke_delete_record<'a>(
+    accounts: delete_record::Accounts<AccountInfo<'a>>,
+    params: delete_record::Params,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let multisig_signers = keys(accounts.multisig_signers);
+    let ix = instruction::delete_record(
+        delete_record::Accounts {
+            system_program: accounts.system_program.key,
+            spl_name_service_program: accounts.spl_name_service_program.key,
+            fee_payer: accounts.fee_payer.key,
+            record: accounts.record.key,
+            domain: accounts.domain.key,
+            domain_owner: accounts.domain_owner.key,
+            central_state: accounts.central_state.key,
+            config: accounts.config.key,
+            domain_token_account: accounts.domain_token_account.map(|a| a.key),
+            multisig_signers: &multisig_signers,
+        },
+        params,
+    );
+    let mut account_infos = vec![
+        accounts.system_program.clone(),
+        accounts.spl_name_service_program.clone(),
+        accounts.fee_payer.clone(),
+        accounts.record.clone(),
+        accounts.domain.clone(),
+        accounts.domain_owner.clone(),
+        accounts.central_state.clone(),
+        accounts.config.clone(),
+    ];
+    account_infos.extend(accounts.domain_token_account.cloned());
+    account_infos.extend_from_slice(accounts.multisig_signers);
+    invoke_signed(&ix, &account_infos, signer_seeds)
+}
+
+pub fn invoke_write_roa<'a>(
+    accounts: write_roa::Accounts<AccountInfo<'a>>,
+    params: write_roa::Params,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let multisig_signers = keys(accounts.multisig_signers);
+    let ix = instruction::write_roa(
+        write_roa::Accounts {
+            system_program: accounts.system_program.key,
+            spl_name_service_program: accounts.spl_name_service_program.key,
+            fee_payer: accounts.fee_payer.key,
+            record: accounts.record.key,
+            domain: accounts.domain.key,
+            domain_owner: accounts.domain_owner.key,
+            central_state: accounts.central_state.key,
+            config: accounts.config.key,
+            domain_token_account: accounts.domain_token_account.map(|a| a.key),
+            multisig_signers: &multisig_signers,
+        },
+        params,
+    );
+    let mut account_infos = vec![
+        accounts.system_program.clone(),
+        accounts.spl_name_service_program.clone(),
+        accounts.fee_payer.clone(),
+        accounts.record.clone(),
+        accounts.domain.clone(),
+        accounts.domain_owner.clone(),
+        accounts.central_state.clone(),
+        accounts.config.clone(),
+    ];
+    account_infos.extend(accounts.domain_token_account.cloned());
+    account_infos.extend_from_slice(accounts.multisig_signers);
+    invoke_signed(&ix, &account_infos, signer_seeds)
+}
+
+pub fn invoke_validate_solana_signature<'a>(
+    accounts: validate_solana_signature::Accounts<AccountInfo<'a>>,
+    params: validate_solana_signature::Params,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let multisig_signers = keys(accounts.multisig_signers);
+    let ix = instruction::validate_solana_signature(
+        validate_solana_signature::Accounts {
+            system_program: accounts.system_program.key,
+            spl_name_service_program: accounts.spl_name_service_program.key,
+            fee_payer: accounts.fee_payer.key,
+            record: accounts.record.key,
+            domain: accounts.domain.key,
+            domain_owner: accounts.domain_owner.key,
+            central_state: accounts.central_state.key,
+            verifier: accounts.verifier.key,
+            config: accounts.config.key,
+            domain_token_account: accounts.domain_token_account.map(|a| a.key),
+            multisig_signers: &multisig_signers,
+        },
+        params,
+    );
+    let mut account_infos = vec![
+        accounts.system_program.clone(),
+        accounts.spl_name_service_program.clone(),
+        accounts.fee_payer.clone(),
+        accounts.record.clone(),
+        accounts.domain.clone(),
+        accounts.domain_owner.clone(),
+        accounts.central_state.clone(),
+        accounts.verifier.clone(),
+        accounts.config.clone(),
+    ];
+    account_infos.extend(accounts.domain_token_account.cloned());
+    account_infos.extend_from_slice(accounts.multisig_signers);
+    invoke_signed(&ix, &account_infos, signer_seeds)
+}
@@ -0,0 +1,301 @@
+//! Hashing and encoding primitives that are not provided by `solana-program`
+
+/// RIPEMD-160 digest of `data`
+pub fn ripemd160(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_le_bytes());
+
+    for block in padded.chunks_exact(64) {
+        let mut x = [0u32; 16];
+        for (word, bytes) in x.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        ripemd160_compress(&mut state, &x);
+    }
+
+    let mut digest = [0; 20];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(state.iter()) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
+
+const RIPEMD_R: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, //
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8, //
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, //
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2, //
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+const RIPEMD_R_PRIME: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, //
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2, //
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, //
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14, //
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+const RIPEMD_S: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, //
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12, //
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, //
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, //
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+const RIPEMD_S_PRIME: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, //
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11, //
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, //
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8, //
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+const RIPEMD_K: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+const RIPEMD_K_PRIME: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+fn ripemd160_f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+fn ripemd160_compress(state: &mut [u32; 5], x: &[u32; 16]) {
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    let [mut a2, mut b2, mut c2, mut d2, mut e2] = *state;
+
+    for j in 0..80 {
+        let round = j / 16;
+
+        let t = a
+            .wrapping_add(ripemd160_f(round, b, c, d))
+            .wrapping_add(x[RIPEMD_R[j]])
+            .wrapping_add(RIPEMD_K[round])
+            .rotate_left(RIPEMD_S[j])
+            .wrapping_add(e);
+        a = e;
+        e = d;
+        d = c.rotate_left(10);
+        c = b;
+        b = t;
+
+        let t = a2
+            .wrapping_add(ripemd160_f(4 - round, b2, c2, d2))
+            .wrapping_add(x[RIPEMD_R_PRIME[j]])
+            .wrapping_add(RIPEMD_K_PRIME[round])
+            .rotate_left(RIPEMD_S_PRIME[j])
+            .wrapping_add(e2);
+        a2 = e2;
+        e2 = d2;
+        d2 = c2.rotate_left(10);
+        c2 = b2;
+        b2 = t;
+    }
+
+    let t = state[1].wrapping_add(c).wrapping_add(d2);
+    state[1] = state[2].wrapping_add(d).wrapping_add(e2);
+    state[2] = state[3].wrapping_add(e).wrapping_add(a2);
+    state[3] = state[4].wrapping_add(a).wrapping_add(b2);
+    state[4] = state[0].wrapping_add(b).wrapping_add(c2);
+    state[0] = t;
+}
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ value as u32;
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= g;
+            }
+        }
+    }
+    checksum
+}
+
+fn bech32_hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes()
+        .map(|c| c >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|c| c & 31))
+}
+
+/// Regroups `data` from `from` bits to `to` bits words, `None` if the padding is invalid
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let mut result = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    let max = (1u32 << to) - 1;
+    for value in data {
+        acc = (acc << from) | *value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            result.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return None;
+    }
+    Some(result)
+}
+
+/// Decodes a bech32 string (e.g a Cosmos address) into its lowercase human readable part and
+/// data
+///
+/// Follows BIP-173: at most 90 printable US-ASCII characters, either all lowercase or all
+/// uppercase.
+pub fn bech32_decode(s: &str) -> Option<(String, Vec<u8>)> {
+    if s.len() > 90 || s.bytes().any(|c| !(33..=126).contains(&c)) {
+        return None;
+    }
+    if s.bytes().any(|c| c.is_ascii_uppercase()) && s.bytes().any(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    let s = s.to_ascii_lowercase();
+    let separator = s.rfind('1')?;
+    let (hrp, data) = (&s[..separator], &s[separator + 1..]);
+    if hrp.is_empty() || data.len() < 6 {
+        return None;
+    }
+    let values = data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|x| *x == c).map(|p| p as u8))
+        .collect::<Option<Vec<_>>>()?;
+    if bech32_polymod(bech32_hrp_expand(hrp).chain(values.iter().copied())) != 1 {
+        return None;
+    }
+    let data = convert_bits(&values[..values.len() - 6], 5, 8, false)?;
+    Some((hrp.to_owned(), data))
+}
+
+/// Encodes `data` as a bech32 string with the human readable part `hrp`
+pub fn bech32_encode(hrp: &str, data: &[u8]) -> String {
+    let mut values = convert_bits(data, 8, 5, true).unwrap_or_default();
+    let polymod = bech32_polymod(
+        bech32_hrp_expand(hrp)
+            .chain(values.iter().copied())
+            .chain([0; 6].iter().copied()),
+    ) ^ 1;
+    values.extend((0..6).map(|i| ((polymod >> (5 * (5 - i))) & 31) as u8));
+
+    let mut result = String::with_capacity(hrp.len() + 1 + values.len());
+    result.push_str(hrp);
+    result.push('1');
+    result.extend(values.iter().map(|v| BECH32_CHARSET[*v as usize] as char));
+    result
+}
+
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+const BLAKE2B_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// BLAKE2b digest of `data` with a 32 bytes output (as used by Sui), without key
+pub fn blake2b256(data: &[u8]) -> [u8; 32] {
+    blake2b(data)
+}
+
+/// BLAKE2b digest of `data` with an `N` bytes output (RFC 7693), without key
+///
+/// `N` must be between 1 and 64.
+pub fn blake2b<const N: usize>(data: &[u8]) -> [u8; N] {
+    assert!(N > 0 && N <= 64, "BLAKE2b outputs between 1 and 64 bytes");
+    let mut state = BLAKE2B_IV;
+    state[0] ^= 0x01010000 ^ N as u64;
+
+    let block_count = std::cmp::max(1, data.len().div_ceil(128));
+    for i in 0..block_count {
+        let chunk = &data[i * 128..std::cmp::min(data.len(), (i + 1) * 128)];
+        let mut block = [0; 128];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let mut m = [0u64; 16];
+        for (word, bytes) in m.iter_mut().zip(block.chunks_exact(8)) {
+            let mut b = [0; 8];
+            b.copy_from_slice(bytes);
+            *word = u64::from_le_bytes(b);
+        }
+        let counter = (i * 128 + chunk.len()) as u128;
+        blake2b_compress(&mut state, &m, counter, i + 1 == block_count);
+    }
+
+    let mut output = [0; 64];
+    for (bytes, word) in output.chunks_exact_mut(8).zip(state.iter()) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    let mut digest = [0; N];
+    digest.copy_from_slice(&output[..N]);
+    digest
+}
+
+fn blake2b_compress(state: &mut [u64; 8], m: &[u64; 16], counter: u128, last: bool) {
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(state);
+    v[8..].copy_from_slice(&BLAKE2B_IV);
+    v[12] ^= counter as u64;
+    v[13] ^= (counter >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+
+    for round in 0..12 {
+        let s = &BLAKE2B_SIGMA[round % 10];
+        blake2b_g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        blake2b_g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        blake2b_g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        blake2b_g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        blake2b_g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        blake2b_g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        blake2b_g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        blake2b_g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    for i in 0..8 {
+        state[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+#[allow(clippy::many_single_char_names)]
+fn blake2b_g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
@@ -49,6 +49,51 @@ impl PrintProgramError for SnsRecordsError {
             SnsRecordsError::InvalidVerifier => msg!("Error: Invalid verifier"),
             SnsRecordsError::WrongParent => msg!("Error: Wrong parent owner"),
             SnsRecordsError::WrongClass => msg!("Error: Wrong class"),
+            SnsRecordsError::InvalidPrecompileInstruction => {
+                msg!("Error: Invalid signature precompile instruction")
+            }
+            SnsRecordsError::InvalidWebAuthnAssertion => msg!("Error: Invalid WebAuthn assertion"),
+            SnsRecordsError::InvalidCosmosAddress => msg!("Error: Invalid Cosmos address"),
+            SnsRecordsError::CosmosAddressMismatch => msg!("Error: Cosmos address mismatch"),
+            SnsRecordsError::AddressMismatch => {
+                msg!("Error: The derived address does not match the record content")
+            }
+            SnsRecordsError::InvalidAttestorSet => msg!("Error: Invalid attestor set"),
+            SnsRecordsError::NotEnoughAttestors => msg!("Error: Not enough attestor signatures"),
+            SnsRecordsError::InvalidUpgradeAuthority => msg!("Error: Invalid upgrade authority"),
+            SnsRecordsError::InvalidAdmin => msg!("Error: Invalid admin"),
+            SnsRecordsError::ProgramPaused => msg!("Error: The program is paused"),
+            SnsRecordsError::ValidationDisabled => msg!("Error: This validation is disabled"),
+            SnsRecordsError::ContentTooLarge => msg!("Error: The record content is too large"),
+            SnsRecordsError::InstructionPaused => msg!("Error: This instruction is paused"),
+            SnsRecordsError::UploadNotInProgress => {
+                msg!("Error: The record is not being uploaded in chunks")
+            }
+            SnsRecordsError::ContentHashMismatch => {
+                msg!("Error: The record content does not match the declared hash")
+            }
+            SnsRecordsError::InvalidContentEncoding => msg!("Error: Invalid content encoding"),
+            SnsRecordsError::InvalidOffChainPointer => {
+                msg!("Error: Invalid off-chain content pointer")
+            }
+            SnsRecordsError::TooManyEntries => msg!("Error: Too many record entries"),
+            SnsRecordsError::ParentAuthorityDisabled => {
+                msg!("Error: The subdomain did not opt in to the parent authority")
+            }
+            SnsRecordsError::WrongTokenHolder => {
+                msg!("Error: The token account does not hold the domain NFT")
+            }
+            SnsRecordsError::NotEnoughMultisigSigners => msg!("Error: Not enough multisig signers"),
+            SnsRecordsError::UploadInProgress => {
+                msg!("Error: The record is still being uploaded in chunks")
+            }
+            SnsRecordsError::MissingConfig => msg!("Error: The config account is missing"),
+            SnsRecordsError::TokenizedDomainAllocation => {
+                msg!("Error: Records of a tokenized domain can only be allocated once the domain is redeemed")
+            }
+            SnsRecordsError::MultisigDomainAllocation => {
+                msg!("Error: Records of a multisig owned domain can't be allocated")
+            }
         }
     }
 }
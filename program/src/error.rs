@@ -32,6 +32,56 @@ pub enum SnsRecordsError {
     WrongParent,
     #[error("Wrong class")]
     WrongClass,
+    #[error("Invalid signature precompile instruction")]
+    InvalidPrecompileInstruction,
+    #[error("Invalid WebAuthn assertion")]
+    InvalidWebAuthnAssertion,
+    #[error("Invalid Cosmos address")]
+    InvalidCosmosAddress,
+    #[error("Cosmos address mismatch")]
+    CosmosAddressMismatch,
+    #[error("The derived address does not match the record content")]
+    AddressMismatch,
+    #[error("Invalid attestor set")]
+    InvalidAttestorSet,
+    #[error("Not enough attestor signatures")]
+    NotEnoughAttestors,
+    #[error("Invalid upgrade authority")]
+    InvalidUpgradeAuthority,
+    #[error("Invalid admin")]
+    InvalidAdmin,
+    #[error("The program is paused")]
+    ProgramPaused,
+    #[error("This validation is disabled")]
+    ValidationDisabled,
+    #[error("The record content is too large")]
+    ContentTooLarge,
+    #[error("This instruction is paused")]
+    InstructionPaused,
+    #[error("The record is not being uploaded in chunks")]
+    UploadNotInProgress,
+    #[error("The record content does not match the declared hash")]
+    ContentHashMismatch,
+    #[error("Invalid content encoding")]
+    InvalidContentEncoding,
+    #[error("Invalid off-chain content pointer")]
+    InvalidOffChainPointer,
+    #[error("Too many record entries")]
+    TooManyEntries,
+    #[error("The subdomain did not opt in to the parent authority")]
+    ParentAuthorityDisabled,
+    #[error("The token account does not hold the domain NFT")]
+    WrongTokenHolder,
+    #[error("Not enough multisig signers")]
+    NotEnoughMultisigSigners,
+    #[error("The record is still being uploaded in chunks")]
+    UploadInProgress,
+    #[error("The config account is missing")]
+    MissingConfig,
+    #[error("Records of a tokenized domain can only be allocated once the domain is redeemed")]
+    TokenizedDomainAllocation,
+    #[error("Records of a multisig owned domain can't be allocated")]
+    MultisigDomainAllocation,
 }
 
 impl From<SnsRecordsError> for ProgramError {
@@ -0,0 +1,116 @@
+use std::convert::TryInto;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{hash::hashv, log::sol_log_data, pubkey::Pubkey},
+};
+
+use crate::{
+    error::SnsRecordsError,
+    instruction::ProgramInstruction,
+    state::{record::RecordView, validation::Validation},
+};
+
+/// First field of every `sol_log_data` call made by the program, used to tell records events apart
+pub const EVENT_TAG: &[u8] = b"sns-records:event";
+
+/// Prefix of the log lines produced by `sol_log_data`
+pub const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Emitted after every instruction that modifies a record account
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub struct RecordEvent {
+    /// The instruction that produced the event
+    pub operation: ProgramInstruction,
+    /// The record account
+    pub record: Pubkey,
+    /// The domain owning the record
+    pub domain: Pubkey,
+    /// The hashed record name, only known by instructions taking the record name as parameter
+    pub record_name_hash: Option<[u8; 32]>,
+    /// The staleness validation after the instruction
+    pub staleness_validation: Validation,
+    /// The RoA validation after the instruction
+    pub right_of_association_validation: Validation,
+    /// SHA256 of the record content, `None` when the record was not posted or got deleted
+    pub content_hash: Option<[u8; 32]>,
+}
+
+impl RecordEvent {
+    /// Builds an event describing the serialized record `data` (starting after the `NameRecordHeader`)
+    pub fn from_record_data(
+        operation: ProgramInstruction,
+        record: &Pubkey,
+        domain: &Pubkey,
+        record_name_hash: Option<&[u8]>,
+        data: &[u8],
+    ) -> Result<Self, SnsRecordsError> {
+        let view = RecordView::parse(data)?;
+        Ok(Self {
+            operation,
+            record: *record,
+            domain: *domain,
+            record_name_hash: record_name_hash.map(to_hash).transpose()?,
+            staleness_validation: view.staleness_validation()?,
+            right_of_association_validation: view.right_of_association_validation()?,
+            content_hash: Some(hashv(&[view.content]).to_bytes()),
+        })
+    }
+
+    /// Builds an event for a record without content (allocated but not posted, or deleted)
+    pub fn empty(
+        operation: ProgramInstruction,
+        record: &Pubkey,
+        domain: &Pubkey,
+        record_name_hash: Option<&[u8]>,
+    ) -> Result<Self, SnsRecordsError> {
+        Ok(Self {
+            operation,
+            record: *record,
+            domain: *domain,
+            record_name_hash: record_name_hash.map(to_hash).transpose()?,
+            staleness_validation: Validation::None,
+            right_of_association_validation: Validation::None,
+            content_hash: None,
+        })
+    }
+
+    /// The largest serialized event, with both optional hashes set
+    pub const MAX_LEN: usize = 1 + 32 + 32 + 33 + 1 + 1 + 33;
+
+    /// Logs the event, serialized on the stack so that emitting it neither allocates nor panics
+    pub fn emit(&self) {
+        let mut buffer = [0; Self::MAX_LEN];
+        let mut writer = &mut buffer[..];
+        if self.serialize(&mut writer).is_ok() {
+            let len = Self::MAX_LEN - writer.len();
+            sol_log_data(&[EVENT_TAG, &buffer[..len]]);
+        }
+    }
+
+    /// Decodes the fields of a `sol_log_data` call, returns `None` if they do not hold a records event
+    pub fn decode(fields: &[&[u8]]) -> Option<Self> {
+        match fields {
+            [tag, data] if *tag == EVENT_TAG => Self::try_from_slice(data).ok(),
+            _ => None,
+        }
+    }
+
+    /// Decodes a `Program data: ...` transaction log line
+    pub fn decode_log(line: &str) -> Option<Self> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        let fields = line
+            .strip_prefix(PROGRAM_DATA_PREFIX)?
+            .split_whitespace()
+            .map(|f| STANDARD.decode(f))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        let fields = fields.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        Self::decode(&fields)
+    }
+}
+
+fn to_hash(hash: &[u8]) -> Result<[u8; 32], SnsRecordsError> {
+    hash.try_into().map_err(|_| SnsRecordsError::OutOfBound)
+}
@@ -0,0 +1,702 @@
+//! Generation of a Shank compatible IDL from the program definitions
+//!
+//! Instruction accounts are built from the `InstructionsAccount` implementations, arguments from
+//! the Borsh schema of each `Params` struct and errors from `SnsRecordsError`, so that the
+//! checked-in IDL (`idl/sns_records.json`) can be regenerated and verified with
+//! `sns-records idl --check idl/sns_records.json`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use {
+    bonfida_utils::InstructionsAccount,
+    borsh::{
+        schema::{Declaration, Definition, Fields},
+        BorshSchema,
+    },
+    num_traits::FromPrimitive,
+    serde_json::{json, Value},
+    solana_program::{hash::hashv, instruction::AccountMeta, pubkey::Pubkey},
+};
+
+use crate::{
+    error::SnsRecordsError,
+    instruction::{
+        add_record_entry, allocate_and_post_record, allocate_chunked_record, allocate_record,
+        delete_record, edit_record, finalize_record, init_config, refresh_staleness,
+        remove_record_entry, set_attestor_set, set_parent_authority, unverify_entry, unverify_roa,
+        update_config, validate_aptos_signature, validate_cosmos_signature,
+        validate_entry_ethereum_signature, validate_entry_solana_signature,
+        validate_ethereum_attestation, validate_ethereum_signature, validate_secp256r1_signature,
+        validate_solana_signature, validate_sui_signature, write_record_chunk, write_roa,
+        ProgramInstruction,
+    },
+    state::{
+        attestor_set::AttestorSet, config::Config, parent_authority::ParentAuthority,
+        record_header::RecordHeader,
+    },
+};
+
+/// Builds the account list of an instruction from its `Accounts` struct, keyed by field name
+///
+/// Optional accounts are marked with `: optional` and variable length ones with `: many`
+macro_rules! idl_accounts {
+    ($module:ident { $($field:ident $(: $kind:ident)?),* $(,)? }) => {{
+        $(let $field = account_key(stringify!($field));)*
+        let metas = $module::Accounts {
+            $($field: idl_account!($field $($kind)?)),*
+        }
+        .get_accounts_vec();
+        let names = [$((stringify!($field), $field, stringify!($($kind)?))),*];
+        accounts_json(&metas, &names)
+    }};
+}
+
+macro_rules! idl_account {
+    ($field:ident) => {
+        &$field
+    };
+    ($field:ident optional) => {
+        Some(&$field)
+    };
+    ($field:ident many) => {
+        std::slice::from_ref(&$field)
+    };
+}
+
+/// Generates the IDL of the program
+pub fn generate() -> Value {
+    let mut types = BTreeMap::new();
+
+    let mut instructions = vec![];
+    let mut push = |instruction: ProgramInstruction, accounts: Vec<Value>, args: Vec<Value>| {
+        let mut entry = json!({
+            "name": camel_case(&format!("{:?}", instruction)),
+            "accounts": accounts,
+            "args": args,
+            "discriminant": { "type": "u8", "value": instruction as u8 },
+        });
+        let docs = instruction_docs(instruction);
+        if !docs.is_empty() {
+            entry["docs"] = json!(docs);
+        }
+        instructions.push(entry);
+    };
+
+    push(
+        ProgramInstruction::AllocateRecord,
+        idl_accounts!(allocate_record {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+        }),
+        params::<allocate_record::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::AllocateAndPostRecord,
+        idl_accounts!(allocate_and_post_record {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+        }),
+        params::<allocate_and_post_record::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::EditRecord,
+        idl_accounts!(edit_record {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            parent_domain: optional,
+            parent_authority: optional,
+            multisig_signers: many,
+        }),
+        params::<edit_record::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::ValidateSolanaSignature,
+        idl_accounts!(validate_solana_signature {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            verifier,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<validate_solana_signature::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::ValidateEthereumSignature,
+        idl_accounts!(validate_ethereum_signature {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<validate_ethereum_signature::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::DeleteRecord,
+        idl_accounts!(delete_record {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<delete_record::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::WriteRoa,
+        idl_accounts!(write_roa {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<write_roa::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::UnverifyRoa,
+        idl_accounts!(unverify_roa {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            central_state,
+            verifier,
+            config,
+        }),
+        params::<unverify_roa::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::ValidateSecp256r1Signature,
+        idl_accounts!(validate_secp256r1_signature {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            instructions_sysvar,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<validate_secp256r1_signature::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::ValidateCosmosSignature,
+        idl_accounts!(validate_cosmos_signature {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<validate_cosmos_signature::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::ValidateSuiSignature,
+        idl_accounts!(validate_sui_signature {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            instructions_sysvar,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<validate_sui_signature::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::ValidateAptosSignature,
+        idl_accounts!(validate_aptos_signature {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            instructions_sysvar,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<validate_aptos_signature::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::SetAttestorSet,
+        idl_accounts!(set_attestor_set {
+            system_program,
+            fee_payer,
+            attestor_set,
+            config,
+            admin,
+        }),
+        params::<set_attestor_set::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::ValidateEthereumAttestation,
+        idl_accounts!(validate_ethereum_attestation {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            attestor_set,
+            config,
+            domain_token_account: optional,
+            attestors: many,
+        }),
+        params::<validate_ethereum_attestation::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::InitConfig,
+        idl_accounts!(init_config {
+            system_program,
+            fee_payer,
+            config,
+            authority,
+            program_data,
+        }),
+        params::<init_config::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::UpdateConfig,
+        idl_accounts!(update_config { config, admin }),
+        params::<update_config::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::AllocateChunkedRecord,
+        idl_accounts!(allocate_chunked_record {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+        }),
+        params::<allocate_chunked_record::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::WriteRecordChunk,
+        idl_accounts!(write_record_chunk {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<write_record_chunk::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::FinalizeRecord,
+        idl_accounts!(finalize_record {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<finalize_record::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::AddRecordEntry,
+        idl_accounts!(add_record_entry {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            parent_domain: optional,
+            parent_authority: optional,
+            multisig_signers: many,
+        }),
+        params::<add_record_entry::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::RemoveRecordEntry,
+        idl_accounts!(remove_record_entry {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            parent_domain: optional,
+            parent_authority: optional,
+            multisig_signers: many,
+        }),
+        params::<remove_record_entry::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::ValidateEntrySolanaSignature,
+        idl_accounts!(validate_entry_solana_signature {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            verifier,
+            config,
+        }),
+        params::<validate_entry_solana_signature::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::ValidateEntryEthereumSignature,
+        idl_accounts!(validate_entry_ethereum_signature {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<validate_entry_ethereum_signature::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::UnverifyEntry,
+        idl_accounts!(unverify_entry {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            record,
+            domain,
+            central_state,
+            verifier,
+            config,
+        }),
+        params::<unverify_entry::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::RefreshStaleness,
+        idl_accounts!(refresh_staleness {
+            system_program,
+            spl_name_service_program,
+            fee_payer,
+            domain,
+            domain_owner,
+            central_state,
+            config,
+            domain_token_account: optional,
+            records: many,
+            multisig_signers: many,
+        }),
+        params::<refresh_staleness::Params>(&mut types),
+    );
+    push(
+        ProgramInstruction::SetParentAuthority,
+        idl_accounts!(set_parent_authority {
+            system_program,
+            fee_payer,
+            domain,
+            domain_owner,
+            parent_authority,
+            config,
+            domain_token_account: optional,
+            multisig_signers: many,
+        }),
+        params::<set_parent_authority::Params>(&mut types),
+    );
+
+    let record_header = struct_type::<RecordHeader>(&mut types);
+    let attestor_set = struct_type::<AttestorSet>(&mut types);
+    let config = struct_type::<Config>(&mut types);
+    let parent_authority = struct_type::<ParentAuthority>(&mut types);
+
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "name": "sns_records",
+        "instructions": instructions,
+        "accounts": [{
+            "name": "RecordHeader",
+            "docs": [
+                "Stored right after the 96 bytes NameRecordHeader of the record account",
+                "Followed by the staleness id, the RoA id and the content, the length of the ids depends on their validation type",
+                "Breaking layout change: only the low 24 bits of contentLength hold the content length, bits 24 to 27 hold the record flags (1 = incomplete) and bits 28 to 31 the content encoding (0 = none, 1 = deflate, 2 = off-chain, 3 = list)",
+                "Deflate content is a raw RFC 1951 stream, validation messages commit to the decoded content",
+            ],
+            "type": record_header,
+        }, {
+            "name": "AttestorSet",
+            "docs": [
+                "Program account at the PDA of [\"attestor_set\"], allocated for the maximum number of attestors",
+            ],
+            "type": attestor_set,
+        }, {
+            "name": "Config",
+            "docs": [
+                "Program account at the PDA of [central_state, \"config\"], defaults apply while it is not initialized",
+            ],
+            "type": config,
+        }, {
+            "name": "ParentAuthority",
+            "docs": [
+                "Program account at the PDA of [\"parent_authority\", subdomain], the opt-in lapses when the subdomain is transferred",
+            ],
+            "type": parent_authority,
+        }],
+        "types": types.into_iter().map(|(name, ty)| json!({ "name": name, "type": ty })).collect::<Vec<_>>(),
+        "errors": errors(),
+        "metadata": {
+            "origin": "shank",
+            "address": crate::ID.to_string(),
+        },
+    })
+}
+
+const ALLOCATION_DOC: &str = "The domain owner must sign directly, the records of a tokenized domain can only be allocated once the domain is redeemed and those of a multisig owned domain can't be allocated. The parent domain owner can't allocate them in parent authority mode either";
+
+const TRAILING_ENCODING_DOC: &str = "The contentEncoding byte may be left out of the instruction data, as older clients do, and then defaults to None";
+
+/// The limitations of an instruction that its accounts and args don't convey
+fn instruction_docs(instruction: ProgramInstruction) -> &'static [&'static str] {
+    match instruction {
+        ProgramInstruction::AllocateRecord | ProgramInstruction::AllocateChunkedRecord => {
+            &[ALLOCATION_DOC]
+        }
+        ProgramInstruction::AllocateAndPostRecord => &[ALLOCATION_DOC, TRAILING_ENCODING_DOC],
+        ProgramInstruction::EditRecord => &[TRAILING_ENCODING_DOC],
+        ProgramInstruction::ValidateEthereumAttestation => &[
+            "The program doesn't check that the contract wallet approved the record message, it trusts the attestors co-signing the transaction to have checked it for the address in the params",
+        ],
+        _ => &[],
+    }
+}
+
+fn account_key(name: &str) -> Pubkey {
+    Pubkey::new_from_array(hashv(&[name.as_bytes()]).to_bytes())
+}
+
+fn accounts_json(metas: &[AccountMeta], names: &[(&str, Pubkey, &str)]) -> Vec<Value> {
+    metas
+        .iter()
+        .map(|meta| {
+            let (name, _, kind) = names.iter().find(|(_, k, _)| *k == meta.pubkey).unwrap();
+            let mut account = json!({
+                "name": camel_case(name),
+                "isMut": meta.is_writable,
+                "isSigner": meta.is_signer,
+            });
+            match *kind {
+                "optional" => account["isOptional"] = json!(true),
+                "many" => account["docs"] = json!(["Any number of trailing accounts"]),
+                _ => {}
+            }
+            account
+        })
+        .collect()
+}
+
+fn errors() -> Vec<Value> {
+    (0..)
+        .map_while(|code| SnsRecordsError::from_u32(code).map(|e| (code, e)))
+        .map(|(code, e)| {
+            json!({
+                "code": code,
+                "name": format!("{:?}", e),
+                "msg": e.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The fields of a `Params` struct, which are serialized right after the instruction tag
+fn params<T: BorshSchema>(types: &mut BTreeMap<String, Value>) -> Vec<Value> {
+    let container = T::schema_container();
+    match container.definitions.get(&container.declaration) {
+        Some(Definition::Struct {
+            fields: Fields::NamedFields(fields),
+        }) => fields
+            .iter()
+            .map(|(name, declaration)| {
+                json!({
+                    "name": camel_case(name),
+                    "type": idl_type(declaration, &container.definitions, types),
+                })
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn struct_type<T: BorshSchema>(types: &mut BTreeMap<String, Value>) -> Value {
+    let container = T::schema_container();
+    defined_type(&container.declaration, &container.definitions, types)
+}
+
+fn idl_type(
+    declaration: &Declaration,
+    definitions: &HashMap<Declaration, Definition>,
+    types: &mut BTreeMap<String, Value>,
+) -> Value {
+    match declaration.as_str() {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" | "bool"
+        | "string" => return json!(declaration),
+        "Pubkey" => return json!("publicKey"),
+        _ => {}
+    }
+    match definitions.get(declaration) {
+        Some(Definition::Sequence { elements }) => {
+            json!({ "vec": idl_type(elements, definitions, types) })
+        }
+        Some(Definition::Array { length, elements }) => {
+            json!({ "array": [idl_type(elements, definitions, types), length] })
+        }
+        Some(Definition::Enum { variants }) if declaration.starts_with("Option<") => {
+            json!({ "option": idl_type(&variants[1].1, definitions, types) })
+        }
+        _ => {
+            if !types.contains_key(declaration) {
+                let ty = defined_type(declaration, definitions, types);
+                types.insert(declaration.clone(), ty);
+            }
+            json!({ "defined": declaration })
+        }
+    }
+}
+
+fn defined_type(
+    declaration: &Declaration,
+    definitions: &HashMap<Declaration, Definition>,
+    types: &mut BTreeMap<String, Value>,
+) -> Value {
+    match definitions.get(declaration) {
+        Some(Definition::Struct { fields }) => json!({
+            "kind": "struct",
+            "fields": fields_json(fields, definitions, types),
+        }),
+        Some(Definition::Enum { variants }) => json!({
+            "kind": "enum",
+            "variants": variants
+                .iter()
+                .map(|(name, declaration)| match definitions.get(declaration) {
+                    Some(Definition::Struct { fields }) if *fields != Fields::Empty => json!({
+                        "name": name,
+                        "fields": fields_json(fields, definitions, types),
+                    }),
+                    _ => json!({ "name": name }),
+                })
+                .collect::<Vec<_>>(),
+        }),
+        _ => panic!("Unsupported declaration {}", declaration),
+    }
+}
+
+fn fields_json(
+    fields: &Fields,
+    definitions: &HashMap<Declaration, Definition>,
+    types: &mut BTreeMap<String, Value>,
+) -> Vec<Value> {
+    match fields {
+        Fields::NamedFields(fields) => fields
+            .iter()
+            .map(|(name, declaration)| {
+                json!({
+                    "name": camel_case(name),
+                    "type": idl_type(declaration, definitions, types),
+                })
+            })
+            .collect(),
+        Fields::UnnamedFields(fields) => fields
+            .iter()
+            .map(|declaration| idl_type(declaration, definitions, types))
+            .collect(),
+        Fields::Empty => vec![],
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for (i, c) in name.chars().enumerate() {
+        if c == '_' {
+            upper = true;
+        } else if i == 0 {
+            result.extend(c.to_lowercase());
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
@@ -0,0 +1,344 @@
+//! Off-chain reconstruction of the records of domains from raw account snapshots
+//!
+//! The store is fed with `(pubkey, data)` pairs of accounts owned by the name service, in any
+//! order. Records are the accounts whose class is the records central state, their parent is
+//! the domain they belong to. Staleness and RoA are evaluated at query time against the
+//! current owner of the domain, if its account was provided.
+//!
+//! Domains whose owner is not a plain key need extra snapshots to be evaluated: the token
+//! account holding the NFT of tokenized domains, and the `XDomain` account of domains owned on
+//! another chain.
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use {
+    borsh::BorshDeserialize,
+    sns_warp_common::state::x_domain::XDomain,
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    spl_name_service::state::NameRecordHeader,
+    spl_token::state::Account as TokenAccount,
+};
+
+use crate::{
+    error::SnsRecordsError,
+    record_kind::RecordKind,
+    state::{
+        record::DecodedRecord, record_header::RecordHeader, record_list::RecordEntry,
+        validation::Validation,
+    },
+    utils::{get_domain_mint, get_record_key_and_seeds, get_tokenizer_vault},
+};
+
+/// Freshness of a record with regards to the current domain owner
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Staleness {
+    /// The staleness was never validated
+    Unverified,
+    /// The staleness id matches the current domain owner
+    Fresh,
+    /// The domain changed hands since the staleness was validated
+    Stale,
+    /// The account of the domain or of its owner (NFT holder, `XDomain`) is missing
+    Unknown,
+}
+
+#[derive(Clone, Debug)]
+#[allow(missing_docs)]
+pub struct DomainEntry {
+    pub owner: Pubkey,
+    pub parent: Pubkey,
+    pub class: Pubkey,
+}
+
+#[derive(Clone, Debug)]
+#[allow(missing_docs)]
+pub struct IndexedRecord {
+    pub key: Pubkey,
+    pub domain: Pubkey,
+    /// The well-known record name, `None` for custom records
+    pub kind: Option<RecordKind>,
+    pub record: DecodedRecord,
+    /// The entries of list records, empty for other records
+    pub entries: Vec<RecordEntry>,
+}
+
+/// A record along with its evaluation against the current domain owner
+#[derive(Clone, Copy, Debug)]
+#[allow(missing_docs)]
+pub struct RecordStatus<'a> {
+    pub key: &'a Pubkey,
+    pub domain: &'a Pubkey,
+    pub kind: Option<RecordKind>,
+    pub record: &'a DecodedRecord,
+    pub entries: &'a [RecordEntry],
+    pub staleness: Staleness,
+    /// The RoA was validated and the record is not stale
+    pub roa_verified: bool,
+}
+
+impl<'a> RecordStatus<'a> {
+    /// The entries of a list record whose RoA was validated, none when the record is not fresh
+    pub fn verified_entries(&self) -> impl Iterator<Item = &'a RecordEntry> {
+        let fresh = self.staleness == Staleness::Fresh;
+        self.entries
+            .iter()
+            .filter(move |e| fresh && is_verified(e.right_of_association_validation))
+    }
+}
+
+/// The holder of a domain NFT, keyed by mint
+#[derive(Clone, Debug)]
+struct NftHolder {
+    token_account: Pubkey,
+    owner: Pubkey,
+}
+
+/// In-memory store of domains and records
+#[derive(Clone, Debug, Default)]
+pub struct RecordStore {
+    domains: HashMap<Pubkey, DomainEntry>,
+    records: HashMap<Pubkey, IndexedRecord>,
+    records_by_domain: HashMap<Pubkey, Vec<Pubkey>>,
+    nft_holders: HashMap<Pubkey, NftHolder>,
+    /// The XChain staleness id of the owner, keyed by `XDomain` account
+    x_chain_owners: HashMap<Pubkey, Vec<u8>>,
+    skipped: HashMap<Pubkey, SnsRecordsError>,
+}
+
+impl RecordStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a store from a set of name service account snapshots
+    ///
+    /// Accounts that cannot be decoded are skipped, see [`RecordStore::skipped_accounts`]
+    pub fn from_accounts<'a, I>(accounts: I) -> Self
+    where
+        I: IntoIterator<Item = (Pubkey, &'a [u8])>,
+    {
+        let mut store = Self::new();
+        for (key, data) in accounts {
+            if let Err(e) = store.insert_account(key, data) {
+                store.skipped.insert(key, e);
+            }
+        }
+        store
+    }
+
+    /// The accounts skipped by [`RecordStore::from_accounts`] and the reason they were skipped
+    pub fn skipped_accounts(&self) -> impl Iterator<Item = (&Pubkey, &SnsRecordsError)> {
+        self.skipped.iter()
+    }
+
+    /// Inserts or replaces a name service account snapshot
+    pub fn insert_account(&mut self, key: Pubkey, data: &[u8]) -> Result<(), SnsRecordsError> {
+        self.skipped.remove(&key);
+        let header = data
+            .get(..NameRecordHeader::LEN)
+            .ok_or(SnsRecordsError::OutOfBound)
+            .and_then(|h| {
+                NameRecordHeader::unpack_from_slice(h)
+                    .map_err(|_| SnsRecordsError::DataTypeMismatch)
+            })?;
+
+        if header.class != crate::central_state::KEY {
+            self.domains.insert(
+                key,
+                DomainEntry {
+                    owner: header.owner,
+                    parent: header.parent_name,
+                    class: header.class,
+                },
+            );
+            return Ok(());
+        }
+
+        let record_header = data
+            .get(NameRecordHeader::LEN..NameRecordHeader::LEN + RecordHeader::LEN)
+            .map(bytemuck::pod_read_unaligned::<RecordHeader>)
+            .ok_or(SnsRecordsError::OutOfBound)?;
+        if record_header.is_incomplete() {
+            // Chunked uploads are only indexed once finalized
+            self.remove_account(&key);
+            return Ok(());
+        }
+        let decoded = DecodedRecord::from_account_data(data)?;
+        let entries = decoded.entries()?.unwrap_or_default();
+
+        let record = IndexedRecord {
+            key,
+            domain: header.parent_name,
+            kind: RecordKind::from_record_key(&header.parent_name, &key),
+            record: decoded,
+            entries,
+        };
+        if let Some(previous) = self.records.insert(key, record) {
+            self.unlink(&previous.domain, &key);
+        }
+        self.records_by_domain
+            .entry(header.parent_name)
+            .or_default()
+            .push(key);
+
+        Ok(())
+    }
+
+    /// Inserts or replaces the snapshot of an SPL token account, used to find the holder of the
+    /// NFT of tokenized domains
+    pub fn insert_token_account(
+        &mut self,
+        key: Pubkey,
+        data: &[u8],
+    ) -> Result<(), SnsRecordsError> {
+        let account = TokenAccount::unpack(data).map_err(|_| SnsRecordsError::DataTypeMismatch)?;
+        if account.amount == 1 {
+            self.nft_holders.insert(
+                account.mint,
+                NftHolder {
+                    token_account: key,
+                    owner: account.owner,
+                },
+            );
+        } else if let Entry::Occupied(e) = self.nft_holders.entry(account.mint) {
+            if e.get().token_account == key {
+                e.remove();
+            }
+        }
+        Ok(())
+    }
+
+    /// Inserts or replaces the snapshot of the `XDomain` account owning a domain from another
+    /// chain
+    pub fn insert_x_domain(&mut self, key: Pubkey, data: &[u8]) -> Result<(), SnsRecordsError> {
+        let x_domain =
+            XDomain::try_from_slice(data).map_err(|_| SnsRecordsError::DataTypeMismatch)?;
+        let mut staleness_id = x_domain.owner_chain.to_le_bytes().to_vec();
+        staleness_id.extend_from_slice(&x_domain.owner_address);
+        self.x_chain_owners.insert(key, staleness_id);
+        Ok(())
+    }
+
+    /// Removes an account (e.g a deleted record) from the store
+    pub fn remove_account(&mut self, key: &Pubkey) {
+        self.skipped.remove(key);
+        self.domains.remove(key);
+        if let Some(record) = self.records.remove(key) {
+            self.unlink(&record.domain, key);
+        }
+    }
+
+    pub fn domain(&self, domain: &Pubkey) -> Option<&DomainEntry> {
+        self.domains.get(domain)
+    }
+
+    pub fn domain_owner(&self, domain: &Pubkey) -> Option<&Pubkey> {
+        self.domains.get(domain).map(|d| &d.owner)
+    }
+
+    /// Iterates over all the domains that have at least one record
+    pub fn domains_with_records(&self) -> impl Iterator<Item = &Pubkey> {
+        self.records_by_domain.keys()
+    }
+
+    pub fn record(&self, key: &Pubkey) -> Option<RecordStatus<'_>> {
+        self.records.get(key).map(|r| self.evaluate(r))
+    }
+
+    /// Looks up a record by domain and record name (e.g `"SOL"`)
+    pub fn domain_record(&self, domain: &Pubkey, record: &str) -> Option<RecordStatus<'_>> {
+        let (key, _) = get_record_key_and_seeds(domain, record);
+        self.records
+            .get(&key)
+            .filter(|r| r.domain == *domain)
+            .map(|r| self.evaluate(r))
+    }
+
+    /// All the records of a domain
+    pub fn domain_records(&self, domain: &Pubkey) -> Vec<RecordStatus<'_>> {
+        self.records_by_domain
+            .get(domain)
+            .into_iter()
+            .flatten()
+            .filter_map(|k| self.records.get(k))
+            .map(|r| self.evaluate(r))
+            .collect()
+    }
+
+    /// The records of a domain that are fresh and have a verified RoA, or at least one verified
+    /// entry for list records
+    pub fn verified_domain_records(&self, domain: &Pubkey) -> Vec<RecordStatus<'_>> {
+        let mut records = self.domain_records(domain);
+        records.retain(|r| r.roa_verified || r.verified_entries().next().is_some());
+        records
+    }
+
+    fn evaluate<'a>(&'a self, indexed: &'a IndexedRecord) -> RecordStatus<'a> {
+        let staleness = self.staleness(indexed);
+        let roa_verified = staleness == Staleness::Fresh
+            && is_verified(indexed.record.right_of_association_validation);
+        RecordStatus {
+            key: &indexed.key,
+            domain: &indexed.domain,
+            kind: indexed.kind,
+            record: &indexed.record,
+            entries: &indexed.entries,
+            staleness,
+            roa_verified,
+        }
+    }
+
+    fn staleness(&self, indexed: &IndexedRecord) -> Staleness {
+        let owner = match self.domain_owner(&indexed.domain) {
+            Some(owner) => owner,
+            None => return Staleness::Unknown,
+        };
+        let expected_id = match indexed.record.staleness_validation {
+            Validation::None => return Staleness::Unverified,
+            Validation::Solana => self
+                .solana_owner(&indexed.domain, owner)
+                .map(|o| o.as_ref()),
+            Validation::XChain => self.x_chain_owners.get(owner).map(|id| &id[..]),
+            _ => None,
+        };
+        match expected_id {
+            Some(id) if indexed.record.staleness_id == id => Staleness::Fresh,
+            Some(_) => Staleness::Stale,
+            None => Staleness::Unknown,
+        }
+    }
+
+    /// The key stamping the staleness of a domain owned on Solana: the holder of the NFT for
+    /// tokenized domains, the owner otherwise (including SPL Token multisigs)
+    fn solana_owner<'a>(&'a self, domain: &Pubkey, owner: &'a Pubkey) -> Option<&'a Pubkey> {
+        if *owner != get_tokenizer_vault() {
+            return Some(owner);
+        }
+        self.nft_holders
+            .get(&get_domain_mint(domain))
+            .map(|holder| &holder.owner)
+    }
+
+    fn unlink(&mut self, domain: &Pubkey, key: &Pubkey) {
+        if let Entry::Occupied(mut e) = self.records_by_domain.entry(*domain) {
+            e.get_mut().retain(|k| k != key);
+            if e.get().is_empty() {
+                e.remove();
+            }
+        }
+    }
+}
+
+/// Whether `validation` is a verified RoA, as opposed to no RoA or a merely written one
+fn is_verified(validation: Validation) -> bool {
+    matches!(
+        validation,
+        Validation::Solana
+            | Validation::Ethereum
+            | Validation::Secp256r1
+            | Validation::Cosmos
+            | Validation::Sui
+            | Validation::Aptos
+            | Validation::EthereumAttested
+    )
+}
@@ -1,6 +1,11 @@
 pub use crate::processor::{
-    allocate_and_post_record, allocate_record, delete_record, edit_record, unverify_roa,
-    validate_ethereum_signature, validate_solana_signature, write_roa,
+    add_record_entry, allocate_and_post_record, allocate_chunked_record, allocate_record,
+    delete_record, edit_record, finalize_record, init_config, refresh_staleness,
+    remove_record_entry, set_attestor_set, set_parent_authority, unverify_entry, unverify_roa,
+    update_config, validate_aptos_signature, validate_cosmos_signature,
+    validate_entry_ethereum_signature, validate_entry_solana_signature,
+    validate_ethereum_attestation, validate_ethereum_signature, validate_secp256r1_signature,
+    validate_solana_signature, validate_sui_signature, write_record_chunk, write_roa,
 };
 use {
     bonfida_utils::InstructionsAccount,
@@ -9,105 +14,421 @@ use {
     solana_program::{instruction::Instruction, pubkey::Pubkey},
 };
 #[allow(missing_docs)]
-#[derive(BorshDeserialize, BorshSerialize, FromPrimitive)]
+#[derive(BorshDeserialize, BorshSerialize, FromPrimitive, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ProgramInstruction {
     /// Allocate record account
-    /// 
-    /// | Index | Writable | Signer | Description                       |
-    /// | ------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The system program account        |
-    /// | 1     | ❌        | ❌      | The SPL token program account     |
-    /// | 2     | ✅        | ✅      | The fee payer account             |
-    /// | 3     | ✅        | ❌      | The record account to create      |
-    /// | 4     | ✅        | ❌      | The domain name owning the record |
-    /// | 5     | ✅        | ✅      | The domain owner                  |
-    /// | 6     | ❌        | ❌      | The SNS Record central state      |
+    ///
+    /// The domain owner must sign directly, the records of a tokenized domain can only be
+    /// allocated once the domain is redeemed and those of a multisig owned domain can't be
+    /// allocated. The parent domain owner can't allocate them in parent authority mode either.
+    ///
+    /// | Index | Writable | Signer | Description                                                         |
+    /// | ----------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                          |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                       |
+    /// | 2     | ✅        | ✅      | The fee payer account                                               |
+    /// | 3     | ✅        | ❌      | The record account to create                                        |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                   |
+    /// | 5     | ✅        | ✅      | The domain owner                                                    |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                        |
+    /// | 7     | ❌        | ❌      | The program config account, also accepted after the other accounts  |
     AllocateRecord,
     /// Allocate record account
-    /// 
-    /// | Index | Writable | Signer | Description                           |
-    /// | ----------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The system program account            |
-    /// | 1     | ❌        | ❌      | The SPL token program account         |
-    /// | 2     | ✅        | ✅      | The fee payer account                 |
-    /// | 3     | ✅        | ❌      | The record account to create and post |
-    /// | 4     | ✅        | ❌      | The domain name owning the record     |
-    /// | 5     | ✅        | ✅      | The domain owner                      |
-    /// | 6     | ❌        | ❌      | The SNS Record central state          |
+    ///
+    /// The domain owner must sign directly, the records of a tokenized domain can only be
+    /// allocated once the domain is redeemed and those of a multisig owned domain can't be
+    /// allocated. The parent domain owner can't allocate them in parent authority mode either.
+    ///
+    /// | Index | Writable | Signer | Description                                                         |
+    /// | ----------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                          |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                       |
+    /// | 2     | ✅        | ✅      | The fee payer account                                               |
+    /// | 3     | ✅        | ❌      | The record account to create and post                               |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                   |
+    /// | 5     | ✅        | ✅      | The domain owner                                                    |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                        |
+    /// | 7     | ❌        | ❌      | The program config account, also accepted after the other accounts  |
     AllocateAndPostRecord,
     /// Edit the record content
-    /// 
-    /// | Index | Writable | Signer | Description                   |
-    /// | --------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The system program account    |
-    /// | 1     | ❌        | ❌      | The SPL token program account |
-    /// | 2     | ✅        | ✅      | The fee payer account         |
-    /// | 3     | ✅        | ❌      | The record account to edit    |
-    /// | 4     | ✅        | ❌      |                               |
-    /// | 5     | ✅        | ✅      |                               |
-    /// | 6     | ❌        | ❌      |                               |
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account to edit                                             |
+    /// | 4     | ✅        | ❌      | The domain name owning record                                          |
+    /// | 5     | ✅        | ✅      | The domain owner, or the parent domain owner                           |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account, also accepted after the other accounts     |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9     | ❌        | ❌      | The parent domain, only in parent authority mode                       |
+    /// | 10    | ❌        | ❌      | The parent authority account, only in parent authority mode            |
+    /// | 11..  | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
     EditRecord,
     /// Validate a RoA or Staleness via Solana signature
-    /// 
-    /// | Index | Writable | Signer | Description                           |
-    /// | ----------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The system program account            |
-    /// | 1     | ❌        | ❌      | The SPL token program account         |
-    /// | 2     | ✅        | ✅      | The fee payer account                 |
-    /// | 3     | ✅        | ❌      | The record account to create and post |
-    /// | 4     | ✅        | ❌      | The domain name owning the record     |
-    /// | 5     | ✅        | ❌      | The domain owner                      |
-    /// | 6     | ❌        | ❌      | The SNS Record central state          |
-    /// | 7     | ✅        | ✅      | The RoA/Staleness verifier public key |
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account to create and post                                  |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ❌      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ✅        | ✅      | The RoA/Staleness verifier public key                                  |
+    /// | 8     | ❌        | ❌      | The program config account, also accepted after the other accounts     |
+    /// | 9     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 10..  | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
     ValidateSolanaSignature,
     /// Validate and ETH signature
-    /// 
-    /// | Index | Writable | Signer | Description                           |
-    /// | ----------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The system program account            |
-    /// | 1     | ❌        | ❌      | The SPL token program account         |
-    /// | 2     | ✅        | ✅      | The fee payer account                 |
-    /// | 3     | ✅        | ❌      | The record account to create and post |
-    /// | 4     | ✅        | ❌      | The domain name owning the record     |
-    /// | 5     | ✅        | ✅      | The domain owner                      |
-    /// | 6     | ❌        | ❌      | The SNS Record central state          |
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account to create and post                                  |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account, also accepted after the other accounts     |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9..   | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
     ValidateEthereumSignature,
     /// Delete a record account
-    /// 
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account to delete                                           |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account, also accepted after the other accounts     |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9..   | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    DeleteRecord,
+    /// Write a RoA in the record
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account to create and post                                  |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account, also accepted after the other accounts     |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9..   | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    WriteRoa,
+    /// Unverify a RoA in the record
+    ///
+    /// | Index | Writable | Signer | Description                                                         |
+    /// | ----------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                          |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                       |
+    /// | 2     | ✅        | ✅      | The fee payer account                                               |
+    /// | 3     | ✅        | ❌      | The record account to create and post                               |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                   |
+    /// | 5     | ❌        | ❌      | The SNS Record central state                                        |
+    /// | 6     | ✅        | ✅      | The current ROA verifier                                            |
+    /// | 7     | ❌        | ❌      | The program config account, also accepted after the other accounts  |
+    UnverifyRoa,
+    /// Validate a RoA via a passkey (WebAuthn secp256r1 assertion)
+    ///
+    /// Must be preceded by a secp256r1 precompile instruction verifying the assertion
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account to validate                                         |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The instructions sysvar                                                |
+    /// | 8     | ❌        | ❌      | The program config account                                             |
+    /// | 9     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 10..  | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    ValidateSecp256r1Signature,
+    /// Validate a RoA via a Cosmos ADR-36 signature, the record content must be the bech32
+    /// address
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account to validate                                         |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account                                             |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9..   | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    ValidateCosmosSignature,
+    /// Validate a RoA via a Sui personal message signature, the record content must be the
+    /// Sui address
+    ///
+    /// Must be preceded by an ed25519 precompile instruction verifying the signature
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account to validate                                         |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The instructions sysvar                                                |
+    /// | 8     | ❌        | ❌      | The program config account                                             |
+    /// | 9     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 10..  | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    ValidateSuiSignature,
+    /// Validate a RoA via an Aptos message signature, the record content must be the Aptos
+    /// address
+    ///
+    /// Must be preceded by an ed25519 precompile instruction verifying the signature
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account to validate                                         |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The instructions sysvar                                                |
+    /// | 8     | ❌        | ❌      | The program config account                                             |
+    /// | 9     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 10..  | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    ValidateAptosSignature,
+    /// Create or update the attestor set vouching for Ethereum contract wallets
+    ///
+    /// The set is created and updated by the config admin
+    ///
+    /// | Index | Writable | Signer | Description                |
+    /// | ------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The system program account |
+    /// | 1     | ✅        | ✅      | The fee payer account      |
+    /// | 2     | ✅        | ❌      | The attestor set account   |
+    /// | 3     | ❌        | ❌      | The program config account |
+    /// | 4     | ❌        | ✅      | The config admin           |
+    SetAttestorSet,
+    /// Validate the RoA of an Ethereum contract wallet (e.g Safe) vouched for by the attestors
+    ///
+    /// The program doesn't check that the wallet approved the record message, it trusts the
+    /// attestors co-signing the transaction to have checked it for the address in the params
+    ///
+    /// | Index | Writable | Signer | Description                                                        |
+    /// | ---------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                         |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                      |
+    /// | 2     | ✅        | ✅      | The fee payer account                                              |
+    /// | 3     | ✅        | ❌      | The record account to validate                                     |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                  |
+    /// | 5     | ✅        | ✅      | The domain owner                                                   |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                       |
+    /// | 7     | ❌        | ❌      | The attestor set account                                           |
+    /// | 8     | ❌        | ❌      | The program config account                                         |
+    /// | 9     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode |
+    /// | 10..  | ❌        | ✅      | The attestors, and the signers of a multisig domain owner          |
+    ValidateEthereumAttestation,
+    /// Create the program config account, signed by the program upgrade authority
+    ///
+    /// | Index | Writable | Signer | Description                    |
+    /// | ---------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account     |
+    /// | 1     | ✅        | ✅      | The fee payer account          |
+    /// | 2     | ✅        | ❌      | The program config account     |
+    /// | 3     | ❌        | ✅      | The program upgrade authority  |
+    /// | 4     | ❌        | ❌      | The program data account       |
+    InitConfig,
+    /// Update the program config account
+    ///
+    /// | Index | Writable | Signer | Description                |
+    /// | ------------------------------------------------------ |
+    /// | 0     | ✅        | ❌      | The program config account |
+    /// | 1     | ❌        | ✅      | The config admin           |
+    UpdateConfig,
+    /// Allocate a record whose content is uploaded in chunks, the record stays flagged as
+    /// incomplete until it is finalized
+    ///
+    /// The domain owner must sign directly, the records of a tokenized domain can only be
+    /// allocated once the domain is redeemed and those of a multisig owned domain can't be
+    /// allocated. The parent domain owner can't allocate them in parent authority mode either.
+    ///
     /// | Index | Writable | Signer | Description                       |
     /// | ------------------------------------------------------------- |
     /// | 0     | ❌        | ❌      | The system program account        |
     /// | 1     | ❌        | ❌      | The SPL token program account     |
     /// | 2     | ✅        | ✅      | The fee payer account             |
-    /// | 3     | ✅        | ❌      | The record account to delete      |
+    /// | 3     | ✅        | ❌      | The record account to create      |
     /// | 4     | ✅        | ❌      | The domain name owning the record |
     /// | 5     | ✅        | ✅      | The domain owner                  |
     /// | 6     | ❌        | ❌      | The SNS Record central state      |
-    DeleteRecord,
-    /// Write a RoA in the record
-    /// 
-    /// | Index | Writable | Signer | Description                           |
-    /// | ----------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The system program account            |
-    /// | 1     | ❌        | ❌      | The SPL token program account         |
-    /// | 2     | ✅        | ✅      | The fee payer account                 |
-    /// | 3     | ✅        | ❌      | The record account to create and post |
-    /// | 4     | ✅        | ❌      | The domain name owning the record     |
-    /// | 5     | ✅        | ✅      | The domain owner                      |
-    /// | 6     | ❌        | ❌      | The SNS Record central state          |
-    WriteRoa,
-    /// Unverify a RoA in the record
-    /// 
+    /// | 7     | ❌        | ❌      | The program config account        |
+    AllocateChunkedRecord,
+    /// Write a chunk at an offset of the content of an incomplete record
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account being uploaded                                      |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account                                             |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9..   | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    WriteRecordChunk,
+    /// Check the content of an incomplete record against its declared hash and mark it complete
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The record account being uploaded                                      |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account                                             |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9..   | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    FinalizeRecord,
+    /// Append an unverified entry to a list record
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The list record account                                                |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner, or the parent domain owner                           |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account                                             |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9     | ❌        | ❌      | The parent domain, only in parent authority mode                       |
+    /// | 10    | ❌        | ❌      | The parent authority account, only in parent authority mode            |
+    /// | 11..  | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    AddRecordEntry,
+    /// Remove an entry from a list record
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The list record account                                                |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner, or the parent domain owner                           |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account                                             |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9     | ❌        | ❌      | The parent domain, only in parent authority mode                       |
+    /// | 10    | ❌        | ❌      | The parent authority account, only in parent authority mode            |
+    /// | 11..  | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    RemoveRecordEntry,
+    /// Validate the RoA of a list record entry via Solana signature
+    ///
+    /// | Index | Writable | Signer | Description                                |
+    /// | ---------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                 |
+    /// | 1     | ❌        | ❌      | The SPL token program account              |
+    /// | 2     | ✅        | ✅      | The fee payer account                      |
+    /// | 3     | ✅        | ❌      | The list record account                    |
+    /// | 4     | ✅        | ❌      | The domain name owning the record          |
+    /// | 5     | ✅        | ❌      | The domain owner                           |
+    /// | 6     | ❌        | ❌      | The SNS Record central state               |
+    /// | 7     | ✅        | ✅      | The entry address, signing for its own RoA |
+    /// | 8     | ❌        | ❌      | The program config account                 |
+    ValidateEntrySolanaSignature,
+    /// Validate the RoA of a list record entry via ETH signature
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The list record account                                                |
+    /// | 4     | ✅        | ❌      | The domain name owning the record                                      |
+    /// | 5     | ✅        | ✅      | The domain owner                                                       |
+    /// | 6     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 7     | ❌        | ❌      | The program config account                                             |
+    /// | 8     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 9..   | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    ValidateEntryEthereumSignature,
+    /// Unverify the RoA of a list record entry
+    ///
     /// | Index | Writable | Signer | Description                           |
     /// | ----------------------------------------------------------------- |
     /// | 0     | ❌        | ❌      | The system program account            |
     /// | 1     | ❌        | ❌      | The SPL token program account         |
     /// | 2     | ✅        | ✅      | The fee payer account                 |
-    /// | 3     | ✅        | ❌      | The record account to create and post |
+    /// | 3     | ✅        | ❌      | The list record account               |
     /// | 4     | ✅        | ❌      | The domain name owning the record     |
     /// | 5     | ❌        | ❌      | The SNS Record central state          |
-    /// | 6     | ✅        | ✅      | The current ROA verifier              |
-    UnverifyRoa,
+    /// | 6     | ✅        | ✅      | The current RoA verifier of the entry |
+    /// | 7     | ❌        | ❌      | The program config account            |
+    UnverifyEntry,
+    /// Re-stamp the staleness of records with the new domain owner, clearing their RoA
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 2     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 3     | ✅        | ❌      | The domain name owning the records                                     |
+    /// | 4     | ✅        | ✅      | The new domain owner                                                   |
+    /// | 5     | ❌        | ❌      | The SNS Record central state                                           |
+    /// | 6     | ❌        | ❌      | The program config account                                             |
+    /// | 7     | ❌        | ❌      | The token account holding the domain NFT, only in token-gated mode     |
+    /// | 8..   | ✅        | ❌      | The record accounts to refresh                                         |
+    /// | ...   | ❌        | ✅      | The signers of the domain owner, only when it is an SPL Token multisig |
+    RefreshStaleness,
+    /// Opt a subdomain in or out of the parent authority mode
+    ///
+    /// The parent domain owner can then edit, validate and delete the records of the subdomain,
+    /// but not allocate them: the name service requires the subdomain owner to sign the creation
+    /// of a record account.
+    ///
+    /// | Index | Writable | Signer | Description                                                               |
+    /// | ----------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                                |
+    /// | 1     | ✅        | ✅      | The fee payer account                                                     |
+    /// | 2     | ❌        | ❌      | The subdomain                                                             |
+    /// | 3     | ❌        | ✅      | The subdomain owner, or the holder of its NFT in token-gated mode         |
+    /// | 4     | ✅        | ❌      | The parent authority account of the subdomain                             |
+    /// | 5     | ❌        | ❌      | The program config account                                                |
+    /// | 6     | ❌        | ❌      | The token account holding the subdomain NFT, only in token-gated mode     |
+    /// | 7..   | ❌        | ✅      | The signers of the subdomain owner, only when it is an SPL Token multisig |
+    SetParentAuthority,
+}
+impl ProgramInstruction {
+    /// Whether the instruction can be paused through the program config, the admin
+    /// instructions never are so that a pause can always be lifted
+    pub fn is_pausable(self) -> bool {
+        !matches!(
+            self,
+            ProgramInstruction::SetAttestorSet
+                | ProgramInstruction::InitConfig
+                | ProgramInstruction::UpdateConfig
+        )
+    }
 }
 #[allow(missing_docs)]
 pub fn allocate_record(
@@ -131,36 +452,41 @@ pub fn edit_record(
     accounts: edit_record::Accounts<Pubkey>,
     params: edit_record::Params,
 ) -> Instruction {
-    accounts.get_instruction(crate::ID, ProgramInstruction::EditRecord as u8, params)
+    let ix = accounts.get_instruction(crate::ID, ProgramInstruction::EditRecord as u8, params);
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
 }
 pub fn validate_ethereum_signature(
     accounts: validate_ethereum_signature::Accounts<Pubkey>,
     params: validate_ethereum_signature::Params,
 ) -> Instruction {
-    accounts.get_instruction(
+    let ix = accounts.get_instruction(
         crate::ID,
         ProgramInstruction::ValidateEthereumSignature as u8,
         params,
-    )
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
 }
 pub fn validate_solana_signature(
     accounts: validate_solana_signature::Accounts<Pubkey>,
     params: validate_solana_signature::Params,
 ) -> Instruction {
-    accounts.get_instruction(
+    let ix = accounts.get_instruction(
         crate::ID,
         ProgramInstruction::ValidateSolanaSignature as u8,
         params,
-    )
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
 }
 pub fn delete_record(
     accounts: delete_record::Accounts<Pubkey>,
     params: delete_record::Params,
 ) -> Instruction {
-    accounts.get_instruction(crate::ID, ProgramInstruction::DeleteRecord as u8, params)
+    let ix = accounts.get_instruction(crate::ID, ProgramInstruction::DeleteRecord as u8, params);
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
 }
 pub fn write_roa(accounts: write_roa::Accounts<Pubkey>, params: write_roa::Params) -> Instruction {
-    accounts.get_instruction(crate::ID, ProgramInstruction::WriteRoa as u8, params)
+    let ix = accounts.get_instruction(crate::ID, ProgramInstruction::WriteRoa as u8, params);
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
 }
 pub fn unverify_roa(
     accounts: unverify_roa::Accounts<Pubkey>,
@@ -168,3 +494,206 @@ pub fn unverify_roa(
 ) -> Instruction {
     accounts.get_instruction(crate::ID, ProgramInstruction::UnverifyRoa as u8, params)
 }
+#[allow(missing_docs)]
+pub fn validate_secp256r1_signature(
+    accounts: validate_secp256r1_signature::Accounts<Pubkey>,
+    params: validate_secp256r1_signature::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateSecp256r1Signature as u8,
+        params,
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn validate_cosmos_signature(
+    accounts: validate_cosmos_signature::Accounts<Pubkey>,
+    params: validate_cosmos_signature::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateCosmosSignature as u8,
+        params,
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn validate_sui_signature(
+    accounts: validate_sui_signature::Accounts<Pubkey>,
+    params: validate_sui_signature::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateSuiSignature as u8,
+        params,
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn validate_aptos_signature(
+    accounts: validate_aptos_signature::Accounts<Pubkey>,
+    params: validate_aptos_signature::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateAptosSignature as u8,
+        params,
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn set_attestor_set(
+    accounts: set_attestor_set::Accounts<Pubkey>,
+    params: set_attestor_set::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::SetAttestorSet as u8, params)
+}
+#[allow(missing_docs)]
+pub fn validate_ethereum_attestation(
+    accounts: validate_ethereum_attestation::Accounts<Pubkey>,
+    params: validate_ethereum_attestation::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateEthereumAttestation as u8,
+        params,
+    )
+}
+#[allow(missing_docs)]
+pub fn init_config(
+    accounts: init_config::Accounts<Pubkey>,
+    params: init_config::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::InitConfig as u8, params)
+}
+#[allow(missing_docs)]
+pub fn update_config(
+    accounts: update_config::Accounts<Pubkey>,
+    params: update_config::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::UpdateConfig as u8, params)
+}
+#[allow(missing_docs)]
+pub fn allocate_chunked_record(
+    accounts: allocate_chunked_record::Accounts<Pubkey>,
+    params: allocate_chunked_record::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::AllocateChunkedRecord as u8,
+        params,
+    )
+}
+#[allow(missing_docs)]
+pub fn write_record_chunk(
+    accounts: write_record_chunk::Accounts<Pubkey>,
+    params: write_record_chunk::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::WriteRecordChunk as u8,
+        params,
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn finalize_record(
+    accounts: finalize_record::Accounts<Pubkey>,
+    params: finalize_record::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(crate::ID, ProgramInstruction::FinalizeRecord as u8, params);
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn add_record_entry(
+    accounts: add_record_entry::Accounts<Pubkey>,
+    params: add_record_entry::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(crate::ID, ProgramInstruction::AddRecordEntry as u8, params);
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn remove_record_entry(
+    accounts: remove_record_entry::Accounts<Pubkey>,
+    params: remove_record_entry::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::RemoveRecordEntry as u8,
+        params,
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn validate_entry_solana_signature(
+    accounts: validate_entry_solana_signature::Accounts<Pubkey>,
+    params: validate_entry_solana_signature::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateEntrySolanaSignature as u8,
+        params,
+    )
+}
+#[allow(missing_docs)]
+pub fn validate_entry_ethereum_signature(
+    accounts: validate_entry_ethereum_signature::Accounts<Pubkey>,
+    params: validate_entry_ethereum_signature::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ValidateEntryEthereumSignature as u8,
+        params,
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn unverify_entry(
+    accounts: unverify_entry::Accounts<Pubkey>,
+    params: unverify_entry::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::UnverifyEntry as u8, params)
+}
+#[allow(missing_docs)]
+pub fn refresh_staleness(
+    accounts: refresh_staleness::Accounts<Pubkey>,
+    params: refresh_staleness::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::RefreshStaleness as u8,
+        params,
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+#[allow(missing_docs)]
+pub fn set_parent_authority(
+    accounts: set_parent_authority::Accounts<Pubkey>,
+    params: set_parent_authority::Params,
+) -> Instruction {
+    let ix = accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::SetParentAuthority as u8,
+        params,
+    );
+    multisig_owner_signed(ix, accounts.domain_owner, accounts.multisig_signers)
+}
+
+/// Clears the signer flag of a multisig domain owner, whose signers sign in its place
+///
+/// The builders taking `multisig_signers` already call it. `validate_ethereum_attestation` mixes
+/// the signers of a multisig owner with the attestors, so its callers call it themselves.
+pub fn multisig_owner_signed(
+    mut ix: Instruction,
+    domain_owner: &Pubkey,
+    multisig_signers: &[Pubkey],
+) -> Instruction {
+    if !multisig_signers.is_empty() {
+        ix.accounts
+            .iter_mut()
+            .filter(|meta| meta.pubkey == *domain_owner)
+            .for_each(|meta| meta.is_signer = false);
+    }
+    ix
+}
@@ -4,6 +4,8 @@ use bonfida_utils::declare_id_with_central_state;
 pub mod entrypoint;
 #[doc(hidden)]
 pub mod error;
+/// Structured events emitted for indexers
+pub mod events;
 /// Program instructions and their CPI-compatible bindings
 pub mod instruction;
 /// Describes the different data structres that the program uses to encode state
@@ -11,12 +13,29 @@ pub mod state;
 
 pub mod utils;
 
+/// Off-chain indexing of record accounts
+#[cfg(feature = "indexer")]
+pub mod indexer;
+/// Well-known record names and their reverse lookup
+pub mod record_kind;
+
+/// Human friendly serde encodings
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+
+/// Shank compatible IDL generation
+#[cfg(feature = "idl")]
+pub mod idl;
+
 #[doc(hidden)]
 pub(crate) mod processor;
 
 #[allow(missing_docs)]
 pub mod cpi;
 
+/// Hashing and encoding primitives
+pub mod crypto;
+
 declare_id_with_central_state!("HP3D4D1ZCmohQGFVms2SS4LCANgJyksBf5s1F77FuFjZ");
 
 #[cfg(not(feature = "no-entrypoint"))]
@@ -7,15 +7,36 @@ use {
     },
 };
 
-use crate::instruction::ProgramInstruction;
+use crate::{
+    instruction::ProgramInstruction,
+    state::config::{Config, ConfigAccount},
+};
 
+pub mod add_record_entry;
 pub mod allocate_and_post_record;
+pub mod allocate_chunked_record;
 pub mod allocate_record;
 pub mod delete_record;
 pub mod edit_record;
+pub mod finalize_record;
+pub mod init_config;
+pub mod refresh_staleness;
+pub mod remove_record_entry;
+pub mod set_attestor_set;
+pub mod set_parent_authority;
+pub mod unverify_entry;
 pub mod unverify_roa;
+pub mod update_config;
+pub mod validate_aptos_signature;
+pub mod validate_cosmos_signature;
+pub mod validate_entry_ethereum_signature;
+pub mod validate_entry_solana_signature;
+pub mod validate_ethereum_attestation;
 pub mod validate_ethereum_signature;
+pub mod validate_secp256r1_signature;
 pub mod validate_solana_signature;
+pub mod validate_sui_signature;
+pub mod write_record_chunk;
 pub mod write_roa;
 
 pub struct Processor {}
@@ -27,51 +48,176 @@ impl Processor {
         instruction_data: &[u8],
     ) -> ProgramResult {
         msg!("Beginning processing");
-        let instruction = FromPrimitive::from_u8(instruction_data[0])
+        let instruction: ProgramInstruction = FromPrimitive::from_u8(instruction_data[0])
             .ok_or(ProgramError::InvalidInstructionData)?;
         let instruction_data = &instruction_data[1..];
         msg!("Instruction unpacked");
 
+        if instruction.is_pausable() {
+            let config = Config::find_in(accounts, program_id)?;
+            config.check_not_paused(instruction)?;
+            return Self::process_pausable(
+                program_id,
+                accounts,
+                instruction_data,
+                instruction,
+                &config,
+            );
+        }
+
+        match instruction {
+            ProgramInstruction::SetAttestorSet => {
+                msg!("[+] Instruction: Set attestor set");
+                let params = set_attestor_set::Params::try_from_slice(instruction_data)?;
+                set_attestor_set::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::InitConfig => {
+                msg!("[+] Instruction: Init config");
+                let params = init_config::Params::try_from_slice(instruction_data)?;
+                init_config::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::UpdateConfig => {
+                msg!("[+] Instruction: Update config");
+                let params = update_config::Params::try_from_slice(instruction_data)?;
+                update_config::process(program_id, accounts, params)?;
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        }
+
+        Ok(())
+    }
+
+    /// Processes an instruction checked against the pause switch, along with the config it was
+    /// checked against
+    fn process_pausable(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+        instruction: ProgramInstruction,
+        config: &ConfigAccount,
+    ) -> ProgramResult {
         match instruction {
             ProgramInstruction::AllocateRecord => {
                 msg!("[+] Instruction: Allocate record");
                 let params = allocate_record::Params::try_from_slice(instruction_data)?;
-                allocate_record::process(program_id, accounts, params)?;
+                allocate_record::process(program_id, accounts, params, config)?;
             }
             ProgramInstruction::AllocateAndPostRecord => {
                 msg!("[+] Instruction: Allocate and post record");
                 let params = allocate_and_post_record::Params::try_from_slice(instruction_data)?;
-                allocate_and_post_record::process(program_id, accounts, params)?;
+                allocate_and_post_record::process(program_id, accounts, params, config)?;
             }
             ProgramInstruction::EditRecord => {
                 msg!("[+] Instruction: Edit record");
                 let params = edit_record::Params::try_from_slice(instruction_data)?;
-                edit_record::process(program_id, accounts, params)?;
+                edit_record::process(program_id, accounts, params, config)?;
             }
             ProgramInstruction::ValidateSolanaSignature => {
                 msg!("[+] Instruction: Validate Solana signature");
                 let params = validate_solana_signature::Params::try_from_slice(instruction_data)?;
-                validate_solana_signature::process(program_id, accounts, params)?;
+                validate_solana_signature::process(program_id, accounts, params, config)?;
             }
             ProgramInstruction::ValidateEthereumSignature => {
                 msg!("[+] Instruction: Validate Ethereum signature");
                 let params = validate_ethereum_signature::Params::try_from_slice(instruction_data)?;
-                validate_ethereum_signature::process(program_id, accounts, params)?;
+                validate_ethereum_signature::process(program_id, accounts, params, config)?;
             }
             ProgramInstruction::DeleteRecord => {
                 msg!("[+] Instruction: Delete record");
                 let params = delete_record::Params::try_from_slice(instruction_data)?;
-                delete_record::process(program_id, accounts, params)?;
+                delete_record::process(program_id, accounts, params, config)?;
             }
             ProgramInstruction::WriteRoa => {
                 msg!("[+] Instruction: Write RoA");
                 let params = write_roa::Params::try_from_slice(instruction_data)?;
-                write_roa::process(program_id, accounts, params)?;
+                write_roa::process(program_id, accounts, params, config)?;
             }
             ProgramInstruction::UnverifyRoa => {
                 msg!("[+] Instruction: Unverify RoA");
-                unverify_roa::process(program_id, accounts)?;
+                unverify_roa::process(program_id, accounts, config)?;
+            }
+            ProgramInstruction::ValidateSecp256r1Signature => {
+                msg!("[+] Instruction: Validate Secp256r1 signature");
+                let params =
+                    validate_secp256r1_signature::Params::try_from_slice(instruction_data)?;
+                validate_secp256r1_signature::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::ValidateCosmosSignature => {
+                msg!("[+] Instruction: Validate Cosmos signature");
+                let params = validate_cosmos_signature::Params::try_from_slice(instruction_data)?;
+                validate_cosmos_signature::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::ValidateSuiSignature => {
+                msg!("[+] Instruction: Validate Sui signature");
+                let params = validate_sui_signature::Params::try_from_slice(instruction_data)?;
+                validate_sui_signature::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::ValidateAptosSignature => {
+                msg!("[+] Instruction: Validate Aptos signature");
+                let params = validate_aptos_signature::Params::try_from_slice(instruction_data)?;
+                validate_aptos_signature::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::ValidateEthereumAttestation => {
+                msg!("[+] Instruction: Validate Ethereum attestation");
+                let params =
+                    validate_ethereum_attestation::Params::try_from_slice(instruction_data)?;
+                validate_ethereum_attestation::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::AllocateChunkedRecord => {
+                msg!("[+] Instruction: Allocate chunked record");
+                let params = allocate_chunked_record::Params::try_from_slice(instruction_data)?;
+                allocate_chunked_record::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::WriteRecordChunk => {
+                msg!("[+] Instruction: Write record chunk");
+                let params = write_record_chunk::Params::try_from_slice(instruction_data)?;
+                write_record_chunk::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::FinalizeRecord => {
+                msg!("[+] Instruction: Finalize record");
+                let params = finalize_record::Params::try_from_slice(instruction_data)?;
+                finalize_record::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::AddRecordEntry => {
+                msg!("[+] Instruction: Add record entry");
+                let params = add_record_entry::Params::try_from_slice(instruction_data)?;
+                add_record_entry::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::RemoveRecordEntry => {
+                msg!("[+] Instruction: Remove record entry");
+                let params = remove_record_entry::Params::try_from_slice(instruction_data)?;
+                remove_record_entry::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::ValidateEntrySolanaSignature => {
+                msg!("[+] Instruction: Validate entry Solana signature");
+                let params =
+                    validate_entry_solana_signature::Params::try_from_slice(instruction_data)?;
+                validate_entry_solana_signature::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::ValidateEntryEthereumSignature => {
+                msg!("[+] Instruction: Validate entry ETH signature");
+                let params =
+                    validate_entry_ethereum_signature::Params::try_from_slice(instruction_data)?;
+                validate_entry_ethereum_signature::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::UnverifyEntry => {
+                msg!("[+] Instruction: Unverify entry");
+                let params = unverify_entry::Params::try_from_slice(instruction_data)?;
+                unverify_entry::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::RefreshStaleness => {
+                msg!("[+] Instruction: Refresh staleness");
+                let params = refresh_staleness::Params::try_from_slice(instruction_data)?;
+                refresh_staleness::process(program_id, accounts, params, config)?;
+            }
+            ProgramInstruction::SetParentAuthority => {
+                msg!("[+] Instruction: Set parent authority");
+                let params = set_parent_authority::Params::try_from_slice(instruction_data)?;
+                set_parent_authority::process(program_id, accounts, params, config)?;
             }
+            ProgramInstruction::SetAttestorSet
+            | ProgramInstruction::InitConfig
+            | ProgramInstruction::UpdateConfig => return Err(ProgramError::InvalidInstructionData),
         }
 
         Ok(())
@@ -1,14 +1,20 @@
 //! Allocate record account
 
+use std::{
+    convert::TryInto,
+    io::{self, Read},
+};
+
 use bonfida_utils::checks::check_account_owner;
 
 use crate::{
-    state::{record_header::RecordHeader, validation::Validation},
-    utils::{check_domain_owner, get_record_key_and_seeds},
+    error::SnsRecordsError,
+    state::{config::ConfigAccount, content_encoding::ContentEncoding, transition},
+    utils::{check_domain_owner_allocation, get_record_key_and_hashed_name, next_config_account},
 };
 
 use {
-    crate::cpi,
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
     bonfida_utils::{
         checks::{check_account_key, check_signer},
         BorshSize, InstructionsAccount,
@@ -24,10 +30,28 @@ use {
     },
 };
 
-#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[derive(BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Params {
     pub record: String,
+    /// The content, encoded with `content_encoding`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::content"))]
     pub content: Vec<u8>,
+    /// The encoding of `content`, may be left out of the instruction data for `None`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub content_encoding: ContentEncoding,
+}
+
+impl BorshDeserialize for Params {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(Self {
+            record: String::deserialize_reader(reader)?,
+            content: Vec::deserialize_reader(reader)?,
+            content_encoding: ContentEncoding::deserialize_trailing(reader)?,
+        })
+    }
 }
 
 #[derive(InstructionsAccount)]
@@ -56,10 +80,16 @@ pub struct Accounts<'a, T> {
 
     /// The SNS Record central state
     pub central_state: &'a T,
+
+    /// The program config account, also accepted after the other accounts
+    pub config: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
-    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
             system_program: next_account_info(accounts_iter)?,
@@ -69,6 +99,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             domain: next_account_info(accounts_iter)?,
             domain_owner: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            config: next_config_account(accounts_iter, accounts, config_key)?,
         };
 
         // Check keys
@@ -82,31 +113,31 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
         // Check signer
         check_signer(accounts.fee_payer)?;
-        check_signer(accounts.domain_owner)?;
 
         Ok(accounts)
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
-    let accounts = Accounts::parse(accounts)?;
-    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+    config.check_content_length(&params.record, params.content.len())?;
 
-    let hashed = crate::utils::get_hashed_name(params.record.as_str());
-    let (key, _) = get_record_key_and_seeds(accounts.domain.key, &params.record);
-    check_account_key(accounts.record, &key)?;
+    check_domain_owner_allocation(accounts.domain, accounts.domain_owner)?;
 
-    let header = RecordHeader {
-        staleness_validation: Validation::None as u16,
-        right_of_association_validation: Validation::None as u16,
-        content_length: params.content.len() as u32,
-    };
+    let (key, hashed) = get_record_key_and_hashed_name(accounts.domain.key, &params.record);
+    check_account_key(accounts.record, &key)?;
 
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &params.content].concat();
+    let data = transition::new_record(&params.content, params.content_encoding)?;
 
     cpi::allocate_record(
-        data.len() as u32,
+        data.len()
+            .try_into()
+            .map_err(|_| SnsRecordsError::NumericalOverflow)?,
         &hashed,
         accounts.record,
         accounts.fee_payer,
@@ -115,7 +146,22 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
         accounts.central_state,
         accounts.system_program,
     )?;
-    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+    cpi::write_record(
+        &data,
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    RecordEvent::from_record_data(
+        ProgramInstruction::AllocateAndPostRecord,
+        accounts.record.key,
+        accounts.domain.key,
+        Some(&hashed),
+        &data,
+    )?
+    .emit();
 
     Ok(())
 }
@@ -0,0 +1,159 @@
+//! Allocate a record whose content is uploaded in chunks
+
+use std::convert::TryInto;
+
+use bonfida_utils::checks::check_account_owner;
+
+use crate::{
+    error::SnsRecordsError,
+    state::{config::ConfigAccount, content_encoding::ContentEncoding, transition},
+    utils::{check_domain_owner_allocation, get_record_key_and_hashed_name},
+};
+
+use {
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    // sns_sdk::record::Record,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Params {
+    pub record: String,
+    /// The length of the final content, once encoded
+    pub content_length: u32,
+    /// The SHA256 of the final content, checked when finalizing the record
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
+    pub content_hash: Vec<u8>,
+    pub content_encoding: ContentEncoding,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to create
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        check_account_key(accounts.config, config_key)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &system_program::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+    config.check_content_length(&params.record, params.content_length as usize)?;
+
+    check_domain_owner_allocation(accounts.domain, accounts.domain_owner)?;
+
+    let (key, hashed) = get_record_key_and_hashed_name(accounts.domain.key, &params.record);
+    check_account_key(accounts.record, &key)?;
+
+    let data = transition::new_chunked_record(
+        params.content_length,
+        &params.content_hash,
+        params.content_encoding,
+    )?;
+
+    cpi::allocate_record(
+        data.len()
+            .try_into()
+            .map_err(|_| SnsRecordsError::NumericalOverflow)?,
+        &hashed,
+        accounts.record,
+        accounts.fee_payer,
+        accounts.domain,
+        accounts.domain_owner,
+        accounts.central_state,
+        accounts.system_program,
+    )?;
+    cpi::write_record(
+        &data,
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    RecordEvent::from_record_data(
+        ProgramInstruction::AllocateChunkedRecord,
+        accounts.record.key,
+        accounts.domain.key,
+        Some(&hashed),
+        &data,
+    )?
+    .emit();
+
+    Ok(())
+}
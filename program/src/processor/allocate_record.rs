@@ -3,12 +3,13 @@
 use bonfida_utils::checks::check_account_owner;
 
 use crate::{
-    state::record_header::RecordHeader,
-    utils::{check_domain_owner, get_record_key_and_seeds},
+    error::SnsRecordsError,
+    state::{config::ConfigAccount, record_header::RecordHeader},
+    utils::{check_domain_owner_allocation, get_record_key_and_hashed_name, next_config_account},
 };
 
 use {
-    crate::cpi,
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
     bonfida_utils::{
         checks::{check_account_key, check_signer},
         BorshSize, InstructionsAccount,
@@ -25,6 +26,9 @@ use {
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Params {
     /// The record length
     pub content_length: u32,
@@ -58,10 +62,16 @@ pub struct Accounts<'a, T> {
 
     /// The SNS Record central state
     pub central_state: &'a T,
+
+    /// The program config account, also accepted after the other accounts
+    pub config: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
-    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
             system_program: next_account_info(accounts_iter)?,
@@ -71,6 +81,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             domain: next_account_info(accounts_iter)?,
             domain_owner: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            config: next_config_account(accounts_iter, accounts, config_key)?,
         };
 
         // Check keys
@@ -84,23 +95,30 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
         // Check signer
         check_signer(accounts.fee_payer)?;
-        check_signer(accounts.domain_owner)?;
 
         Ok(accounts)
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
-    let accounts = Accounts::parse(accounts)?;
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+    config.check_content_length(&params.record, params.content_length as usize)?;
 
-    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_owner_allocation(accounts.domain, accounts.domain_owner)?;
 
-    let hashed = crate::utils::get_hashed_name(params.record.as_str());
-    let (key, _) = get_record_key_and_seeds(accounts.domain.key, &params.record);
+    let (key, hashed) = get_record_key_and_hashed_name(accounts.domain.key, &params.record);
     check_account_key(accounts.record, &key)?;
 
     cpi::allocate_record(
-        params.content_length + RecordHeader::LEN as u32,
+        params
+            .content_length
+            .checked_add(RecordHeader::LEN as u32)
+            .ok_or(SnsRecordsError::NumericalOverflow)?,
         &hashed,
         accounts.record,
         accounts.fee_payer,
@@ -110,5 +128,13 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
         accounts.system_program,
     )?;
 
+    RecordEvent::empty(
+        ProgramInstruction::AllocateRecord,
+        accounts.record.key,
+        accounts.domain.key,
+        Some(&hashed),
+    )?
+    .emit();
+
     Ok(())
 }
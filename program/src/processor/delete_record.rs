@@ -2,10 +2,16 @@
 
 use bonfida_utils::checks::check_account_owner;
 
-use crate::utils::{check_domain_owner, check_domain_parent};
+use crate::{
+    state::config::ConfigAccount,
+    utils::{
+        check_domain_owner_or_holder, check_domain_owner_signer, check_domain_parent,
+        next_config_account, next_domain_token_account,
+    },
+};
 
 use {
-    crate::cpi,
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
     bonfida_utils::{
         checks::{check_account_key, check_signer},
         BorshSize, InstructionsAccount,
@@ -21,6 +27,9 @@ use {
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Params {}
 
 #[derive(InstructionsAccount)]
@@ -49,10 +58,23 @@ pub struct Accounts<'a, T> {
 
     /// The SNS Record central state
     pub central_state: &'a T,
+
+    /// The program config account, also accepted after the other accounts
+    pub config: &'a T,
+
+    /// The token account holding the domain NFT, only in token-gated mode
+    pub domain_token_account: Option<&'a T>,
+
+    #[cons(signer)]
+    /// The signers of the domain owner, only when it is an SPL Token multisig
+    pub multisig_signers: &'a [T],
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
-    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
             system_program: next_account_info(accounts_iter)?,
@@ -62,6 +84,9 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             domain: next_account_info(accounts_iter)?,
             domain_owner: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            config: next_config_account(accounts_iter, accounts, config_key)?,
+            domain_token_account: next_domain_token_account(accounts_iter),
+            multisig_signers: accounts_iter.as_slice(),
         };
 
         // Check keys
@@ -75,16 +100,25 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
         // Check signer
         check_signer(accounts.fee_payer)?;
-        check_signer(accounts.domain_owner)?;
+        check_domain_owner_signer(accounts.domain_owner, accounts.multisig_signers)?;
 
         Ok(accounts)
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
-    let accounts = Accounts::parse(accounts)?;
-
-    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+
+    check_domain_owner_or_holder(
+        accounts.domain,
+        accounts.domain_owner.key,
+        accounts.domain_token_account,
+    )?;
     check_domain_parent(accounts.record, accounts.domain.key)?;
 
     cpi::delete_record(
@@ -93,5 +127,13 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params)
         accounts.domain_owner,
     )?;
 
+    RecordEvent::empty(
+        ProgramInstruction::DeleteRecord,
+        accounts.record.key,
+        accounts.domain.key,
+        None,
+    )?
+    .emit();
+
     Ok(())
 }
@@ -1,16 +1,22 @@
 //! Edit the record content
 
+use std::io::{self, Read};
+
 use bonfida_utils::checks::check_account_owner;
-use solana_program::program_pack::Pack;
-use spl_name_service::state::NameRecordHeader;
 
 use crate::{
-    state::record_header::RecordHeader,
-    utils::{check_domain_owner, check_domain_parent},
+    state::{
+        config::ConfigAccount, content_encoding::ContentEncoding,
+        parent_authority::check_domain_authority, transition,
+    },
+    utils::{
+        check_domain_owner_signer, check_domain_parent, next_account_info_if, next_config_account,
+        next_domain_token_account,
+    },
 };
 
 use {
-    crate::cpi,
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
     bonfida_utils::{
         checks::{check_account_key, check_signer},
         BorshSize, InstructionsAccount,
@@ -25,10 +31,28 @@ use {
     },
 };
 
-#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[derive(BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Params {
     pub record: String,
+    /// The content, encoded with `content_encoding`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::content"))]
     pub content: Vec<u8>,
+    /// The encoding of `content`, may be left out of the instruction data for `None`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub content_encoding: ContentEncoding,
+}
+
+impl BorshDeserialize for Params {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(Self {
+            record: String::deserialize_reader(reader)?,
+            content: Vec::deserialize_reader(reader)?,
+            content_encoding: ContentEncoding::deserialize_trailing(reader)?,
+        })
+    }
 }
 
 #[derive(InstructionsAccount)]
@@ -48,16 +72,38 @@ pub struct Accounts<'a, T> {
     pub record: &'a T,
 
     #[cons(writable)]
+    /// The domain name owning the record
     pub domain: &'a T,
 
     #[cons(writable, signer)]
+    /// The domain owner, or the parent domain owner in parent authority mode
     pub domain_owner: &'a T,
 
+    /// The SNS Record central state
     pub central_state: &'a T,
+
+    /// The program config account, also accepted after the other accounts
+    pub config: &'a T,
+
+    /// The token account holding the domain NFT, only in token-gated mode
+    pub domain_token_account: Option<&'a T>,
+
+    /// The parent domain, only in parent authority mode
+    pub parent_domain: Option<&'a T>,
+
+    /// The parent authority account of the domain, only in parent authority mode
+    pub parent_authority: Option<&'a T>,
+
+    #[cons(signer)]
+    /// The signers of the domain owner, only when it is an SPL Token multisig
+    pub multisig_signers: &'a [T],
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
-    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
             system_program: next_account_info(accounts_iter)?,
@@ -67,6 +113,11 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             domain: next_account_info(accounts_iter)?,
             domain_owner: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            config: next_config_account(accounts_iter, accounts, config_key)?,
+            domain_token_account: next_domain_token_account(accounts_iter),
+            parent_domain: next_account_info_if(accounts_iter, |a| !a.is_signer),
+            parent_authority: next_account_info_if(accounts_iter, |a| !a.is_signer),
+            multisig_signers: accounts_iter.as_slice(),
         };
 
         // Check keys
@@ -80,34 +131,50 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
         // Check signer
         check_signer(accounts.fee_payer)?;
-        check_signer(accounts.domain_owner)?;
+        check_domain_owner_signer(accounts.domain_owner, accounts.multisig_signers)?;
 
         Ok(accounts)
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
-    let accounts = Accounts::parse(accounts)?;
-
-    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+    config.check_content_length(&params.record, params.content.len())?;
+
+    check_domain_authority(
+        program_id,
+        accounts.domain,
+        accounts.domain_owner.key,
+        accounts.domain_token_account,
+        accounts.parent_domain,
+        accounts.parent_authority,
+    )?;
     check_domain_parent(accounts.record, accounts.domain.key)?;
 
-    let header = RecordHeader::new(params.content.len() as u32);
-
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &params.content].concat();
-
-    if accounts.record.data_len() - NameRecordHeader::LEN != data.len() {
-        cpi::resize_record(
-            accounts.record,
-            accounts.central_state,
-            accounts.fee_payer,
-            accounts.system_program,
-            data.len() as u32,
-        )?;
-    }
-
-    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+    let data = transition::new_record(&params.content, params.content_encoding)?;
+
+    cpi::write_record(
+        &data,
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    let hashed = crate::utils::get_hashed_name(&params.record);
+    RecordEvent::from_record_data(
+        ProgramInstruction::EditRecord,
+        accounts.record.key,
+        accounts.domain.key,
+        Some(&hashed),
+        &data,
+    )?
+    .emit();
 
     Ok(())
 }
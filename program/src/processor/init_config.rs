@@ -0,0 +1,118 @@
+//! Create the program config account
+
+use crate::{state::config::Config, utils::check_upgrade_authority};
+
+use {
+    crate::cpi,
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Params {
+    /// The key allowed to update the config
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::pubkey"))]
+    pub admin: Pubkey,
+    /// Whether record mutating instructions are rejected
+    pub paused: bool,
+    /// Bitmask of the paused instructions, indexed by `ProgramInstruction` discriminant
+    pub paused_instructions: u64,
+    /// Bitmask of the allowed RoA validations, indexed by `Validation` discriminant
+    pub enabled_validations: u64,
+    /// The maximum content length of a record, on top of the per record kind limits
+    pub max_content_length: u32,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The program config account
+    pub config: &'a T,
+
+    #[cons(signer)]
+    /// The program upgrade authority
+    pub authority: &'a T,
+
+    /// The program data account
+    pub program_data: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            authority: next_account_info(accounts_iter)?,
+            program_data: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.config, &Config::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.config, &system_program::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.authority)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+    let Params {
+        admin,
+        paused,
+        paused_instructions,
+        enabled_validations,
+        max_content_length,
+    } = params;
+
+    check_upgrade_authority(program_id, accounts.program_data, accounts.authority.key)?;
+
+    cpi::create_program_account(
+        Config::LEN,
+        &[
+            crate::central_state::KEY.as_ref(),
+            Config::SEED,
+            &[Config::NONCE],
+        ],
+        accounts.config,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    let config = Config {
+        admin,
+        paused,
+        paused_instructions,
+        enabled_validations,
+        max_content_length,
+    };
+    config.save(accounts.config)
+}
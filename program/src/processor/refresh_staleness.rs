@@ -0,0 +1,172 @@
+//! Re-stamp the staleness of several records of a domain after a transfer
+
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    state::{config::ConfigAccount, record::RecordView, transition, validation::Validation},
+    utils::{
+        check_domain_owner_or_holder, check_domain_owner_signer, check_domain_parent,
+        next_domain_token_account,
+    },
+};
+
+use {
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the records
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The new domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+
+    /// The token account holding the domain NFT, only in token-gated mode
+    pub domain_token_account: Option<&'a T>,
+
+    #[cons(writable)]
+    /// The record accounts to refresh
+    pub records: &'a [T],
+
+    #[cons(signer)]
+    /// The signers of the domain owner, only when it is an SPL Token multisig
+    pub multisig_signers: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let mut accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            domain_token_account: next_domain_token_account(accounts_iter),
+            records: accounts_iter.as_slice(),
+            multisig_signers: &[],
+        };
+        // The multisig signers follow the records, which cannot sign
+        if let Some(split) = accounts.records.iter().position(|a| a.is_signer) {
+            let (records, multisig_signers) = accounts.records.split_at(split);
+            accounts.records = records;
+            accounts.multisig_signers = multisig_signers;
+        }
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        check_account_key(accounts.config, config_key)?;
+
+        // Check owners
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+        for record in accounts.records {
+            check_account_owner(record, &spl_name_service::ID)?;
+        }
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_domain_owner_signer(accounts.domain_owner, accounts.multisig_signers)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+
+    check_domain_owner_or_holder(
+        accounts.domain,
+        accounts.domain_owner.key,
+        accounts.domain_token_account,
+    )?;
+
+    for record in accounts.records {
+        check_domain_parent(record, accounts.domain.key)?;
+
+        let data = {
+            let record_data = record.data.borrow();
+            let (_, buffer) = record_data.split_at(NameRecordHeader::LEN);
+            // Records already stamped by the owner keep their RoA
+            let current = RecordView::parse(buffer)?;
+            if current.staleness_validation()? == Validation::Solana
+                && current.staleness_id == accounts.domain_owner.key.as_ref()
+            {
+                continue;
+            }
+            transition::validate_staleness(
+                buffer,
+                Validation::Solana,
+                accounts.domain_owner.key.as_ref(),
+            )?
+        };
+
+        cpi::write_record(
+            &data,
+            record,
+            accounts.central_state,
+            accounts.fee_payer,
+            accounts.system_program,
+        )?;
+
+        RecordEvent::from_record_data(
+            ProgramInstruction::RefreshStaleness,
+            record.key,
+            accounts.domain.key,
+            None,
+            &data,
+        )?
+        .emit();
+    }
+
+    Ok(())
+}
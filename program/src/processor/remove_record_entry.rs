@@ -0,0 +1,165 @@
+//! Remove an entry from a list record
+
+use bonfida_utils::checks::check_account_owner;
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    state::{config::ConfigAccount, parent_authority::check_domain_authority, transition},
+    utils::{
+        check_domain_owner_signer, check_domain_parent, next_account_info_if,
+        next_domain_token_account,
+    },
+};
+
+use {
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Params {
+    /// The index of the entry to remove
+    pub index: u16,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The list record account
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner, or the parent domain owner in parent authority mode
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+
+    /// The token account holding the domain NFT, only in token-gated mode
+    pub domain_token_account: Option<&'a T>,
+
+    /// The parent domain, only in parent authority mode
+    pub parent_domain: Option<&'a T>,
+
+    /// The parent authority account of the domain, only in parent authority mode
+    pub parent_authority: Option<&'a T>,
+
+    #[cons(signer)]
+    /// The signers of the domain owner, only when it is an SPL Token multisig
+    pub multisig_signers: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            domain_token_account: next_domain_token_account(accounts_iter),
+            parent_domain: next_account_info_if(accounts_iter, |a| !a.is_signer),
+            parent_authority: next_account_info_if(accounts_iter, |a| !a.is_signer),
+            multisig_signers: accounts_iter.as_slice(),
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        check_account_key(accounts.config, config_key)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_domain_owner_signer(accounts.domain_owner, accounts.multisig_signers)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+
+    check_domain_authority(
+        program_id,
+        accounts.domain,
+        accounts.domain_owner.key,
+        accounts.domain_token_account,
+        accounts.parent_domain,
+        accounts.parent_authority,
+    )?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+
+    let data = {
+        let record_data = accounts.record.data.borrow();
+        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN);
+        transition::remove_entry(buffer, params.index)?
+    };
+
+    cpi::write_record(
+        &data,
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    RecordEvent::from_record_data(
+        ProgramInstruction::RemoveRecordEntry,
+        accounts.record.key,
+        accounts.domain.key,
+        None,
+        &data,
+    )?
+    .emit();
+
+    Ok(())
+}
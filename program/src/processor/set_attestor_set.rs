@@ -0,0 +1,118 @@
+//! Create or update the attestor set vouching for Ethereum contract wallets
+
+use crate::state::{attestor_set::AttestorSet, config::Config};
+
+use {
+    crate::{cpi, error::SnsRecordsError},
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Params {
+    /// The number of distinct attestor signatures required
+    pub threshold: u8,
+    /// The attestor keys
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::pubkeys"))]
+    pub attestors: Vec<Pubkey>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The attestor set account
+    pub attestor_set: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+
+    #[cons(signer)]
+    /// The config admin
+    pub admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            attestor_set: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.attestor_set, &AttestorSet::find_key(program_id).0)?;
+        check_account_key(accounts.config, &Config::KEY)?;
+
+        // Check owners
+        if !accounts.attestor_set.data_is_empty() {
+            check_account_owner(accounts.attestor_set, program_id)?;
+        }
+        check_account_owner(accounts.config, program_id)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let Params {
+        threshold,
+        attestors,
+    } = params;
+
+    let config =
+        Config::from_account_info(accounts.config)?.ok_or(SnsRecordsError::Uninitialized)?;
+    if config.admin != *accounts.admin.key {
+        return Err(SnsRecordsError::InvalidAdmin.into());
+    }
+
+    let attestor_set = AttestorSet {
+        threshold,
+        attestors,
+    };
+    attestor_set.check()?;
+
+    if accounts.attestor_set.data_is_empty() {
+        let (_, nonce) = AttestorSet::find_key(program_id);
+        cpi::create_program_account(
+            AttestorSet::LEN,
+            &[AttestorSet::SEED, &[nonce]],
+            accounts.attestor_set,
+            accounts.fee_payer,
+            accounts.system_program,
+        )?;
+    }
+
+    attestor_set.save(accounts.attestor_set)
+}
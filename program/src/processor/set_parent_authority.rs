@@ -0,0 +1,143 @@
+//! Opt a subdomain in or out of the parent authority mode
+
+use crate::{
+    cpi,
+    state::{config::ConfigAccount, parent_authority::ParentAuthority},
+    utils::{check_domain_owner_or_holder, check_domain_owner_signer, next_domain_token_account},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    spl_name_service::state::NameRecordHeader,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Params {
+    /// Whether the owner of the parent domain may manage the records of the subdomain
+    pub enabled: bool,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    /// The subdomain
+    pub domain: &'a T,
+
+    #[cons(signer)]
+    /// The subdomain owner, or the holder of its NFT in token-gated mode
+    pub domain_owner: &'a T,
+
+    #[cons(writable)]
+    /// The parent authority account of the subdomain
+    pub parent_authority: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+
+    /// The token account holding the subdomain NFT, only in token-gated mode
+    pub domain_token_account: Option<&'a T>,
+
+    #[cons(signer)]
+    /// The signers of the subdomain owner, only when it is an SPL Token multisig
+    pub multisig_signers: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            parent_authority: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            domain_token_account: next_domain_token_account(accounts_iter),
+            multisig_signers: accounts_iter.as_slice(),
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(
+            accounts.parent_authority,
+            &ParentAuthority::find_key(accounts.domain.key, program_id).0,
+        )?;
+        check_account_key(accounts.config, config_key)?;
+
+        // Check owners
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+        if !accounts.parent_authority.data_is_empty() {
+            check_account_owner(accounts.parent_authority, program_id)?;
+        }
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_domain_owner_signer(accounts.domain_owner, accounts.multisig_signers)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id, &config.key)?;
+
+    check_domain_owner_or_holder(
+        accounts.domain,
+        accounts.domain_owner.key,
+        accounts.domain_token_account,
+    )?;
+
+    if accounts.parent_authority.data_is_empty() {
+        let (_, nonce) = ParentAuthority::find_key(accounts.domain.key, program_id);
+        cpi::create_program_account(
+            ParentAuthority::LEN,
+            &[
+                ParentAuthority::SEED,
+                accounts.domain.key.as_ref(),
+                &[nonce],
+            ],
+            accounts.parent_authority,
+            accounts.fee_payer,
+            accounts.system_program,
+        )?;
+    }
+
+    // The opt-in is bound to the owner recorded by the name service, the vault of the name
+    // tokenizer for a tokenized subdomain
+    let owner = if params.enabled {
+        NameRecordHeader::unpack_from_slice(&accounts.domain.data.borrow())?.owner
+    } else {
+        Pubkey::default()
+    };
+    ParentAuthority { owner }.save(accounts.parent_authority)
+}
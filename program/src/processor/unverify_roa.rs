@@ -1,19 +1,14 @@
 //! Unverify a RoA in the record
 
-use std::convert::TryInto;
-
 use spl_name_service::state::NameRecordHeader;
 
 use crate::{
-    error::SnsRecordsError,
-    state::{
-        record_header::RecordHeader,
-        validation::{get_validation_length, Validation},
-    },
+    state::{config::ConfigAccount, transition},
+    utils::next_config_account,
 };
 
 use {
-    crate::cpi,
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
     bonfida_utils::{
         checks::{check_account_key, check_account_owner, check_signer},
         BorshSize, InstructionsAccount,
@@ -30,6 +25,9 @@ use {
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Params {}
 
 #[derive(InstructionsAccount)]
@@ -58,10 +56,16 @@ pub struct Accounts<'a, T> {
     #[cons(writable, signer)]
     /// The current ROA verifier
     pub verifier: &'a T,
+
+    /// The program config account, also accepted after the other accounts
+    pub config: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
-    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
             system_program: next_account_info(accounts_iter)?,
@@ -71,6 +75,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             domain: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
             verifier: next_account_info(accounts_iter)?,
+            config: next_config_account(accounts_iter, accounts, config_key)?,
         };
 
         // Check keys
@@ -89,47 +94,35 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    let accounts = Accounts::parse(accounts)?;
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
 
-    let (new_buffer, current_length, header) = {
+    let data = {
         let record_data = accounts.record.data.borrow();
-        let mut header = RecordHeader::from_buffer(&record_data);
-        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN + RecordHeader::LEN);
-
-        let mut new_buffer: Vec<u8> = vec![];
-
-        let (staleness_id, rest) = buffer
-            .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
-        let (roa, content) = rest.split_at(get_validation_length(
-            header.right_of_association_validation.try_into()?,
-        ) as usize);
-
-        if roa != accounts.verifier.key.as_ref() {
-            return Err(SnsRecordsError::InvalidVerifier.into());
-        }
-
-        header.right_of_association_validation = Validation::None as u16;
-        new_buffer.extend_from_slice(staleness_id);
-        new_buffer.extend_from_slice(content);
-
-        (new_buffer, buffer.len(), header)
+        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN);
+        transition::unverify_roa(buffer, accounts.verifier.key)?
     };
 
-    if new_buffer.len() != current_length {
-        cpi::resize_record(
-            accounts.record,
-            accounts.central_state,
-            accounts.fee_payer,
-            accounts.system_program,
-            (new_buffer.len() + RecordHeader::LEN) as u32,
-        )?;
-    }
-
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &new_buffer].concat();
-
-    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+    cpi::write_record(
+        &data,
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    RecordEvent::from_record_data(
+        ProgramInstruction::UnverifyRoa,
+        accounts.record.key,
+        accounts.domain.key,
+        None,
+        &data,
+    )?
+    .emit();
 
     Ok(())
 }
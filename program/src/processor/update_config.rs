@@ -0,0 +1,99 @@
+//! Update the program config account
+
+use crate::{error::SnsRecordsError, state::config::Config};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+/// The fields to update, `None` leaves the current value unchanged
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Params {
+    /// The new admin
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::option_pubkey"))]
+    pub admin: Option<Pubkey>,
+    /// Whether record mutating instructions are rejected
+    pub paused: Option<bool>,
+    /// Bitmask of the paused instructions, indexed by `ProgramInstruction` discriminant
+    pub paused_instructions: Option<u64>,
+    /// Bitmask of the allowed RoA validations, indexed by `Validation` discriminant
+    pub enabled_validations: Option<u64>,
+    /// The maximum content length of a record, on top of the per record kind limits
+    pub max_content_length: Option<u32>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    #[cons(writable)]
+    /// The program config account
+    pub config: &'a T,
+
+    #[cons(signer)]
+    /// The config admin
+    pub admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            config: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.config, &Config::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.config, program_id)?;
+
+        // Check signer
+        check_signer(accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let mut config =
+        Config::from_account_info(accounts.config)?.ok_or(SnsRecordsError::Uninitialized)?;
+    if config.admin != *accounts.admin.key {
+        return Err(SnsRecordsError::InvalidAdmin.into());
+    }
+
+    if let Some(admin) = params.admin {
+        config.admin = admin;
+    }
+    if let Some(paused) = params.paused {
+        config.paused = paused;
+    }
+    if let Some(paused_instructions) = params.paused_instructions {
+        config.paused_instructions = paused_instructions;
+    }
+    if let Some(enabled_validations) = params.enabled_validations {
+        config.enabled_validations = enabled_validations;
+    }
+    if let Some(max_content_length) = params.max_content_length {
+        config.max_content_length = max_content_length;
+    }
+
+    config.save(accounts.config)
+}
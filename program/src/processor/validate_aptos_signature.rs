@@ -0,0 +1,182 @@
+//! Validate a RoA with a Aptos message signature
+
+use crate::{
+    state::{config::ConfigAccount, record::RecordView, transition, validation::Validation},
+    utils::{
+        check_domain_owner_or_holder, check_domain_owner_signer, check_domain_parent,
+        check_precompile_verification, next_domain_token_account,
+    },
+};
+
+use {
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
+    bonfida_utils::checks::check_account_owner,
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        ed25519_program,
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program, sysvar,
+    },
+    spl_name_service::state::NameRecordHeader,
+    std::convert::TryInto,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Params {
+    /// The Ed25519 public key of the Aptos account
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
+    pub public_key: Vec<u8>,
+}
+
+pub use crate::state::message::aptos_signed_message;
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_name_service_program: &'a T,
+
+    #[cons(writable, signer)]
+    /// The fee payer account
+    pub fee_payer: &'a T,
+
+    #[cons(writable)]
+    /// The record account to validate
+    pub record: &'a T,
+
+    #[cons(writable)]
+    /// The domain name owning the record
+    pub domain: &'a T,
+
+    #[cons(writable, signer)]
+    /// The domain owner
+    pub domain_owner: &'a T,
+
+    /// The SNS Record central state
+    pub central_state: &'a T,
+
+    /// The instructions sysvar, used to find the ed25519 precompile instruction
+    pub instructions_sysvar: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+
+    /// The token account holding the domain NFT, only in token-gated mode
+    pub domain_token_account: Option<&'a T>,
+
+    #[cons(signer)]
+    /// The signers of the domain owner, only when it is an SPL Token multisig
+    pub multisig_signers: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            instructions_sysvar: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            domain_token_account: next_domain_token_account(accounts_iter),
+            multisig_signers: accounts_iter.as_slice(),
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        check_account_key(accounts.instructions_sysvar, &sysvar::instructions::ID)?;
+        check_account_key(accounts.config, config_key)?;
+
+        // Check owners
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_domain_owner_signer(accounts.domain_owner, accounts.multisig_signers)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+    config.check_validation(Validation::Aptos)?;
+    let Params { public_key } = params;
+
+    check_domain_owner_or_holder(
+        accounts.domain,
+        accounts.domain_owner.key,
+        accounts.domain_token_account,
+    )?;
+    check_domain_parent(accounts.record, accounts.domain.key)?;
+
+    let data = {
+        let record_data = accounts.record.data.borrow();
+        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN);
+
+        let record = RecordView::parse(buffer)?;
+        let staleness_id: [u8; 32] = record
+            .staleness_id
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        check_precompile_verification(
+            accounts.instructions_sysvar,
+            &ed25519_program::ID,
+            &public_key,
+            &aptos_signed_message(
+                &record.committed_content()?,
+                accounts.record.key,
+                &staleness_id.into(),
+            ),
+        )?;
+
+        transition::validate_aptos_roa(buffer, &public_key)?
+    };
+
+    cpi::write_record(
+        &data,
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    RecordEvent::from_record_data(
+        ProgramInstruction::ValidateAptosSignature,
+        accounts.record.key,
+        accounts.domain.key,
+        None,
+        &data,
+    )?
+    .emit();
+
+    Ok(())
+}
@@ -1,24 +1,21 @@
 //! Validate and ETH signature
 
 use crate::{
-    error::SnsRecordsError,
-    state::{
-        record_header::RecordHeader,
-        validation::{get_validation_length, Validation},
+    state::{config::ConfigAccount, transition, validation::Validation},
+    utils::{
+        check_domain_owner_or_holder, check_domain_owner_signer, check_domain_parent,
+        next_config_account, next_domain_token_account,
     },
-    utils::{check_domain_owner, check_domain_parent},
 };
 
 use {
-    crate::cpi,
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
     bonfida_utils::checks::check_account_owner,
     bonfida_utils::{
         checks::{check_account_key, check_signer},
         BorshSize, InstructionsAccount,
     },
     borsh::{BorshDeserialize, BorshSerialize},
-    solana_program::keccak::Hasher,
-    solana_program::secp256k1_recover::secp256k1_recover,
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
@@ -28,57 +25,26 @@ use {
         system_program,
     },
     spl_name_service::state::NameRecordHeader,
-    std::convert::TryInto,
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Params {
     /// The type of validation
     pub validation: Validation,
     /// The record enum as a string
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub signature: Vec<u8>,
     /// The expected ETH public key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub expected_pubkey: Vec<u8>,
 }
 
-pub const ETH_PREFIX_BYTES: &[u8; 26] = b"\x19Ethereum Signed Message:\n";
-pub const RECORD_SUFFIX: &[u8; 13] = b"\nFor record: ";
-pub const STALENESS_SUFFIX: &[u8; 15] = b"\nStaleness ID: ";
-
-///
-/// The message to sign must contain the record public key & staleness ID
-// +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
-// | ETH_PREFIX_BYTES       | content_length   | content          | RECORD_SUFFIX    | record_key       | STALENESS_SUFFIX | staleness_id     |
-// +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
-// | "\x19Ethereum Signed   | Length of        | Actual content   | "\nFor record: " | Public key of    | "\nStaleness ID: " | Public key of   |
-// | Message:\n"            | (content +       | to be signed     |                  | the record       |                  | the staleness    |
-// |                        | record_key +     |                  |                  |                  |                  |                  |
-// |                        | RECORD_SUFFIX +  |                  |                  |                  |                  |                  |
-// |                        | staleness_id +   |                  |                  |                  |                  |                  |
-// |                        | STALENESS_SUFFIX)|                  |                  |                  |                  |                  |
-// +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
-fn message_to_sign(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
-    let mut buffer = Vec::new();
-    let record_key_base58 = record_key.to_string();
-    let staleness_id_base58 = staleness_id.to_string();
-    let hex_encoded_content = hex::encode(content);
-
-    let content_length = hex_encoded_content.len()
-        + record_key_base58.len()
-        + staleness_id_base58.len()
-        + RECORD_SUFFIX.len()
-        + STALENESS_SUFFIX.len();
-
-    buffer.extend_from_slice(ETH_PREFIX_BYTES);
-    buffer.extend_from_slice(content_length.to_string().as_bytes());
-    buffer.extend_from_slice(hex_encoded_content.as_bytes());
-    buffer.extend_from_slice(RECORD_SUFFIX);
-    buffer.extend_from_slice(record_key_base58.as_bytes());
-    buffer.extend_from_slice(STALENESS_SUFFIX);
-    buffer.extend_from_slice(staleness_id_base58.as_bytes());
-
-    buffer
-}
+pub use crate::state::message::{
+    message_to_sign, record_message, ETH_PREFIX_BYTES, RECORD_SUFFIX, STALENESS_SUFFIX,
+};
 
 #[derive(InstructionsAccount)]
 pub struct Accounts<'a, T> {
@@ -106,10 +72,23 @@ pub struct Accounts<'a, T> {
 
     /// The SNS Record central state
     pub central_state: &'a T,
+
+    /// The program config account, also accepted after the other accounts
+    pub config: &'a T,
+
+    /// The token account holding the domain NFT, only in token-gated mode
+    pub domain_token_account: Option<&'a T>,
+
+    #[cons(signer)]
+    /// The signers of the domain owner, only when it is an SPL Token multisig
+    pub multisig_signers: &'a [T],
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
-    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
             system_program: next_account_info(accounts_iter)?,
@@ -119,6 +98,9 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             domain: next_account_info(accounts_iter)?,
             domain_owner: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            config: next_config_account(accounts_iter, accounts, config_key)?,
+            domain_token_account: next_domain_token_account(accounts_iter),
+            multisig_signers: accounts_iter.as_slice(),
         };
 
         // Check keys
@@ -132,100 +114,60 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
         // Check signer
         check_signer(accounts.fee_payer)?;
-        check_signer(accounts.domain_owner)?;
+        check_domain_owner_signer(accounts.domain_owner, accounts.multisig_signers)?;
 
         Ok(accounts)
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
-    let accounts = Accounts::parse(accounts)?;
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+    config.check_validation(Validation::Ethereum)?;
     let Params {
         validation: _,
         signature,
         expected_pubkey,
     } = params;
 
-    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_owner_or_holder(
+        accounts.domain,
+        accounts.domain_owner.key,
+        accounts.domain_token_account,
+    )?;
     check_domain_parent(accounts.record, accounts.domain.key)?;
 
-    let (new_buffer, current_length, header) = {
+    let data = {
         let record_data = accounts.record.data.borrow();
-        let mut header = RecordHeader::from_buffer(&record_data);
-        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN + RecordHeader::LEN);
-
-        let (staleness_id, rest) = buffer
-            .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
-        let (_, content) = rest.split_at(get_validation_length(
-            header.right_of_association_validation.try_into()?,
-        ) as usize);
-
-        // Implicitly means that if the staleness is not verified it's
-        // impossible to verify the RoA
-        let staleness_id_array: [u8; 32] = staleness_id
-            .try_into()
-            .map_err(|_| SnsRecordsError::OutOfBound)?;
-        let buffer = message_to_sign(
-            content,
+        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN);
+        transition::validate_ethereum_roa(
+            buffer,
             accounts.record.key,
-            &Pubkey::from(staleness_id_array),
-        );
-
-        let recovery_id = signature[64]
-            .checked_sub(27)
-            .ok_or(SnsRecordsError::NumericalOverflow)?;
-
-        let mut hasher = Hasher::default();
-        hasher.hash(&buffer);
-        let hash = hasher.result();
-
-        let recovered_pubkey = secp256k1_recover(
-            hash.as_ref(),
-            recovery_id,
-            signature.get(0..64).ok_or(SnsRecordsError::OutOfBound)?,
-        )
-        .map_err(|_| SnsRecordsError::Secp256k1Recover)?;
-
-        // Hash the public key using Keccak-256
-        let mut hasher = Hasher::default();
-        hasher.hash(&recovered_pubkey.0);
-        let output = hasher.result();
-
-        // Take the last 20 bytes of the hash to get the Ethereum address
-        let eth_address = output.0.get(12..).ok_or(SnsRecordsError::OutOfBound)?;
-
-        if eth_address != expected_pubkey {
-            return Err(SnsRecordsError::EthPubkeyMismatch.into());
-        }
-
-        /////////
-
-        header.right_of_association_validation = Validation::Ethereum as u16;
-
-        let mut new_buffer: Vec<u8> = vec![];
-        new_buffer.extend_from_slice(staleness_id);
-        new_buffer.extend_from_slice(&expected_pubkey);
-        new_buffer.extend_from_slice(content);
-
-        (new_buffer, buffer.len(), header)
+            &signature,
+            &expected_pubkey,
+        )?
     };
 
-    if new_buffer.len() != current_length {
-        cpi::resize_record(
-            accounts.record,
-            accounts.central_state,
-            accounts.fee_payer,
-            accounts.system_program,
-            (new_buffer.len() + RecordHeader::LEN)
-                .try_into()
-                .map_err(|_| SnsRecordsError::NumericalOverflow)?,
-        )?;
-    }
-
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &new_buffer].concat();
-
-    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+    cpi::write_record(
+        &data,
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    RecordEvent::from_record_data(
+        ProgramInstruction::ValidateEthereumSignature,
+        accounts.record.key,
+        accounts.domain.key,
+        None,
+        &data,
+    )?
+    .emit();
 
     Ok(())
 }
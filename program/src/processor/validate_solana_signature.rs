@@ -1,20 +1,17 @@
 //! Validate a RoA or Staleness via Solana signature
 
-use std::convert::TryInto;
-
 use spl_name_service::state::NameRecordHeader;
 
 use crate::{
-    error::SnsRecordsError,
-    state::{
-        record_header::RecordHeader,
-        validation::{get_validation_length, Validation},
+    state::{config::ConfigAccount, transition, validation::Validation},
+    utils::{
+        check_domain_owner_or_holder, check_domain_owner_signer, check_domain_parent,
+        next_config_account, next_domain_token_account,
     },
-    utils::{check_domain_owner, check_domain_parent},
 };
 
 use {
-    crate::cpi,
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
     bonfida_utils::{
         checks::{check_account_key, check_account_owner, check_signer},
         BorshSize, InstructionsAccount,
@@ -31,6 +28,9 @@ use {
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Params {
     pub staleness: bool,
 }
@@ -65,10 +65,23 @@ pub struct Accounts<'a, T> {
     #[cons(writable, signer)]
     /// The RoA/Staleness verifier public key
     pub verifier: &'a T,
+
+    /// The program config account, also accepted after the other accounts
+    pub config: &'a T,
+
+    /// The token account holding the domain NFT, only in token-gated mode
+    pub domain_token_account: Option<&'a T>,
+
+    #[cons(signer)]
+    /// The signers of the domain owner, only when it is an SPL Token multisig
+    pub multisig_signers: &'a [T],
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
-    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
             system_program: next_account_info(accounts_iter)?,
@@ -79,6 +92,9 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             domain_owner: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
             verifier: next_account_info(accounts_iter)?,
+            config: next_config_account(accounts_iter, accounts, config_key)?,
+            domain_token_account: next_domain_token_account(accounts_iter),
+            multisig_signers: accounts_iter.as_slice(),
         };
 
         // Check keys
@@ -97,72 +113,66 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
-    let accounts = Accounts::parse(accounts)?;
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
     let Params { staleness } = params;
 
-    let (new_buffer, current_length, header) = {
+    let data = {
         let record_data = accounts.record.data.borrow();
-        let mut header = RecordHeader::from_buffer(&record_data);
-        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN + RecordHeader::LEN);
-
-        let mut new_buffer: Vec<u8> = vec![];
+        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN);
 
         if staleness {
-            check_signer(accounts.domain_owner)?;
-            check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+            check_domain_owner_signer(accounts.domain_owner, accounts.multisig_signers)?;
+            check_domain_owner_or_holder(
+                accounts.domain,
+                accounts.domain_owner.key,
+                accounts.domain_token_account,
+            )?;
             check_domain_parent(accounts.record, accounts.domain.key)?;
-            let (_, rest) = buffer
-                .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
 
             if accounts.domain_owner.owner == &sns_warp_common::constants::EMITTER_KEY {
                 // This is a xchain_owned account
-                header.staleness_validation = Validation::XChain as u16;
                 let record = sns_warp_common::state::x_domain::XDomain::try_from_slice(
                     &accounts.domain_owner.data.borrow(),
                 )?;
-                new_buffer.extend_from_slice(&record.owner_chain.to_le_bytes());
-                new_buffer.extend_from_slice(&record.owner_address);
+                let mut staleness_id = Vec::with_capacity(34);
+                staleness_id.extend_from_slice(&record.owner_chain.to_le_bytes());
+                staleness_id.extend_from_slice(&record.owner_address);
+                transition::validate_staleness(buffer, Validation::XChain, &staleness_id)?
             } else {
-                header.staleness_validation = Validation::Solana as u16;
-                new_buffer.extend_from_slice(accounts.verifier.key.as_ref());
+                transition::validate_staleness(
+                    buffer,
+                    Validation::Solana,
+                    accounts.verifier.key.as_ref(),
+                )?
             }
-
-            header.right_of_association_validation = Validation::None as u16;
-            new_buffer.extend_from_slice(rest)
         } else {
-            let (_, rest) = buffer
-                .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
-
-            let expected_verifier = rest.get(..32).ok_or(SnsRecordsError::OutOfBound)?;
-
-            if expected_verifier != accounts.verifier.key.as_ref() {
-                return Err(SnsRecordsError::InvalidVerifier.into());
-            }
-
-            header.right_of_association_validation = Validation::Solana as u16;
-
-            // The buffer remains unchanged
-            new_buffer = buffer.to_vec();
+            config.check_validation(Validation::Solana)?;
+            transition::validate_solana_roa(buffer, accounts.verifier.key)?
         }
-
-        (new_buffer, buffer.len(), header)
     };
 
-    if new_buffer.len() != current_length {
-        cpi::resize_record(
-            accounts.record,
-            accounts.central_state,
-            accounts.fee_payer,
-            accounts.system_program,
-            (new_buffer.len() + RecordHeader::LEN) as u32,
-        )?;
-    }
-
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &new_buffer].concat();
-
-    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+    cpi::write_record(
+        &data,
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    RecordEvent::from_record_data(
+        ProgramInstruction::ValidateSolanaSignature,
+        accounts.record.key,
+        accounts.domain.key,
+        None,
+        &data,
+    )?
+    .emit();
 
     Ok(())
 }
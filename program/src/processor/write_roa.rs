@@ -1,21 +1,19 @@
 //! Write a RoA in the record
 
-use std::convert::TryInto;
-
 use spl_name_service::state::NameRecordHeader;
 
 use crate::{
-    state::{
-        record_header::RecordHeader,
-        validation::{get_validation_length, Validation},
+    state::{config::ConfigAccount, transition, validation::Validation},
+    utils::{
+        check_domain_owner_or_holder, check_domain_owner_signer, check_domain_parent,
+        next_config_account, next_domain_token_account,
     },
-    utils::{check_domain_owner, check_domain_parent},
 };
 
 use {
-    crate::cpi,
+    crate::{cpi, events::RecordEvent, instruction::ProgramInstruction},
     bonfida_utils::{
-        checks::{check_account_key, check_account_owner, check_signer},
+        checks::{check_account_key, check_account_owner},
         BorshSize, InstructionsAccount,
     },
     borsh::{BorshDeserialize, BorshSerialize},
@@ -30,7 +28,11 @@ use {
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Params {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::base58"))]
     pub roa_id: Vec<u8>,
 }
 
@@ -60,10 +62,23 @@ pub struct Accounts<'a, T> {
 
     /// The SNS Record central state
     pub central_state: &'a T,
+
+    /// The program config account, also accepted after the other accounts
+    pub config: &'a T,
+
+    /// The token account holding the domain NFT, only in token-gated mode
+    pub domain_token_account: Option<&'a T>,
+
+    #[cons(signer)]
+    /// The signers of the domain owner, only when it is an SPL Token multisig
+    pub multisig_signers: &'a [T],
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
-    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        config_key: &Pubkey,
+    ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
             system_program: next_account_info(accounts_iter)?,
@@ -73,6 +88,9 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             domain: next_account_info(accounts_iter)?,
             domain_owner: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            config: next_config_account(accounts_iter, accounts, config_key)?,
+            domain_token_account: next_domain_token_account(accounts_iter),
+            multisig_signers: accounts_iter.as_slice(),
         };
 
         // Check keys
@@ -85,54 +103,51 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         check_account_owner(accounts.domain, &spl_name_service::ID)?;
 
         // Check signer
-        check_signer(accounts.domain_owner)?;
+        check_domain_owner_signer(accounts.domain_owner, accounts.multisig_signers)?;
 
         Ok(accounts)
     }
 }
 
-pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
-    let accounts = Accounts::parse(accounts)?;
+pub fn process(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+    config: &ConfigAccount,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, &config.key)?;
+    config.check_validation(Validation::UnverifiedSolana)?;
     let Params { roa_id } = params;
 
-    check_domain_owner(accounts.domain, accounts.domain_owner.key)?;
+    check_domain_owner_or_holder(
+        accounts.domain,
+        accounts.domain_owner.key,
+        accounts.domain_token_account,
+    )?;
     check_domain_parent(accounts.record, accounts.domain.key)?;
 
-    let (new_buffer, current_length, header) = {
+    let data = {
         let record_data = accounts.record.data.borrow();
-        let mut header = RecordHeader::from_buffer(&record_data);
-        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN + RecordHeader::LEN);
-
-        let mut new_buffer: Vec<u8> = vec![];
-
-        let (staleness_id, rest) = buffer
-            .split_at(get_validation_length(header.staleness_validation.try_into()?) as usize);
-        let (_, content) = rest.split_at(get_validation_length(
-            header.right_of_association_validation.try_into()?,
-        ) as usize);
-
-        header.right_of_association_validation = Validation::UnverifiedSolana as u16;
-        new_buffer.extend_from_slice(staleness_id);
-        new_buffer.extend_from_slice(&roa_id);
-        new_buffer.extend_from_slice(content);
-
-        (new_buffer, buffer.len(), header)
+        let (_, buffer) = record_data.split_at(NameRecordHeader::LEN);
+        transition::write_roa(buffer, &roa_id)?
     };
 
-    if new_buffer.len() != current_length {
-        cpi::resize_record(
-            accounts.record,
-            accounts.central_state,
-            accounts.fee_payer,
-            accounts.system_program,
-            (new_buffer.len() + RecordHeader::LEN) as u32,
-        )?;
-    }
-
-    let header_bytes = bytemuck::bytes_of(&header);
-    let data = [header_bytes, &new_buffer].concat();
-
-    cpi::edit_record(&data, 0, accounts.record, accounts.central_state)?;
+    cpi::write_record(
+        &data,
+        accounts.record,
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    RecordEvent::from_record_data(
+        ProgramInstruction::WriteRoa,
+        accounts.record.key,
+        accounts.domain.key,
+        None,
+        &data,
+    )?
+    .emit();
 
     Ok(())
 }
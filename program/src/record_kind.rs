@@ -0,0 +1,200 @@
+//! Well-known record names and their reverse lookup
+//!
+//! Record accounts are derived from the hash of the record name, which makes it impossible to
+//! tell which record an account holds. This table maps the hashes of the well-known record names
+//! back to a compact kind id.
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    error::SnsRecordsError,
+    utils::{get_hashed_name, get_record_key_and_seeds},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+#[allow(missing_docs)]
+pub enum RecordKind {
+    Ipfs,
+    Arwv,
+    Sol,
+    Eth,
+    Btc,
+    Ltc,
+    Doge,
+    Email,
+    Url,
+    Discord,
+    Github,
+    Reddit,
+    Twitter,
+    Telegram,
+    Pic,
+    Shdw,
+    Point,
+    Bsc,
+    Injective,
+    Backpack,
+    A,
+    Aaaa,
+    Cname,
+    Txt,
+    Base,
+}
+
+/// The maximum content length of records that are not well-known
+pub const MAX_CONTENT_LENGTH: u32 = 10_240;
+
+/// The maximum content length of the record named `record`
+pub fn max_content_length(record: &str) -> u32 {
+    record
+        .parse::<RecordKind>()
+        .map_or(MAX_CONTENT_LENGTH, |kind| kind.max_content_length())
+}
+
+impl RecordKind {
+    pub const ALL: [RecordKind; 25] = [
+        RecordKind::Ipfs,
+        RecordKind::Arwv,
+        RecordKind::Sol,
+        RecordKind::Eth,
+        RecordKind::Btc,
+        RecordKind::Ltc,
+        RecordKind::Doge,
+        RecordKind::Email,
+        RecordKind::Url,
+        RecordKind::Discord,
+        RecordKind::Github,
+        RecordKind::Reddit,
+        RecordKind::Twitter,
+        RecordKind::Telegram,
+        RecordKind::Pic,
+        RecordKind::Shdw,
+        RecordKind::Point,
+        RecordKind::Bsc,
+        RecordKind::Injective,
+        RecordKind::Backpack,
+        RecordKind::A,
+        RecordKind::Aaaa,
+        RecordKind::Cname,
+        RecordKind::Txt,
+        RecordKind::Base,
+    ];
+
+    /// The record name used to derive the record account
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecordKind::Ipfs => "IPFS",
+            RecordKind::Arwv => "ARWV",
+            RecordKind::Sol => "SOL",
+            RecordKind::Eth => "ETH",
+            RecordKind::Btc => "BTC",
+            RecordKind::Ltc => "LTC",
+            RecordKind::Doge => "DOGE",
+            RecordKind::Email => "email",
+            RecordKind::Url => "url",
+            RecordKind::Discord => "discord",
+            RecordKind::Github => "github",
+            RecordKind::Reddit => "reddit",
+            RecordKind::Twitter => "twitter",
+            RecordKind::Telegram => "telegram",
+            RecordKind::Pic => "pic",
+            RecordKind::Shdw => "SHDW",
+            RecordKind::Point => "POINT",
+            RecordKind::Bsc => "BSC",
+            RecordKind::Injective => "INJ",
+            RecordKind::Backpack => "backpack",
+            RecordKind::A => "A",
+            RecordKind::Aaaa => "AAAA",
+            RecordKind::Cname => "CNAME",
+            RecordKind::Txt => "TXT",
+            RecordKind::Base => "BASE",
+        }
+    }
+
+    /// The maximum content length, addresses can be stored either raw or as strings
+    pub fn max_content_length(&self) -> u32 {
+        match self {
+            RecordKind::Sol
+            | RecordKind::Eth
+            | RecordKind::Btc
+            | RecordKind::Ltc
+            | RecordKind::Doge
+            | RecordKind::Bsc
+            | RecordKind::Injective
+            | RecordKind::Base
+            | RecordKind::A
+            | RecordKind::Aaaa => 128,
+            RecordKind::Discord
+            | RecordKind::Github
+            | RecordKind::Reddit
+            | RecordKind::Twitter
+            | RecordKind::Telegram
+            | RecordKind::Backpack => 256,
+            RecordKind::Ipfs
+            | RecordKind::Arwv
+            | RecordKind::Email
+            | RecordKind::Url
+            | RecordKind::Pic
+            | RecordKind::Shdw
+            | RecordKind::Point
+            | RecordKind::Cname => 1_024,
+            RecordKind::Txt => 4_096,
+        }
+    }
+
+    pub fn hashed_name(&self) -> Vec<u8> {
+        get_hashed_name(self.as_str())
+    }
+
+    pub fn record_key(&self, domain: &Pubkey) -> Pubkey {
+        get_record_key_and_seeds(domain, self.as_str()).0
+    }
+
+    /// Finds the kind whose hashed name is `hashed_name`
+    pub fn from_hashed_name(hashed_name: &[u8]) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|k| k.hashed_name() == hashed_name)
+            .copied()
+    }
+
+    /// Finds the kind of the record account `record_key` of `domain`
+    pub fn from_record_key(domain: &Pubkey, record_key: &Pubkey) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|k| k.record_key(domain) == *record_key)
+            .copied()
+    }
+}
+
+impl TryFrom<u8> for RecordKind {
+    type Error = SnsRecordsError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::ALL
+            .get(value as usize)
+            .copied()
+            .ok_or(SnsRecordsError::DataTypeMismatch)
+    }
+}
+
+impl FromStr for RecordKind {
+    type Err = SnsRecordsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|k| k.as_str() == s)
+            .copied()
+            .ok_or(SnsRecordsError::DataTypeMismatch)
+    }
+}
+
+impl fmt::Display for RecordKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
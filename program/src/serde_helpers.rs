@@ -0,0 +1,170 @@
+//! Human friendly serde encodings used behind the `serde` feature
+
+use std::str::FromStr;
+
+use {
+    serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer},
+    solana_program::pubkey::Pubkey,
+};
+
+use crate::state::validation::Validation;
+
+/// Public keys as base58 strings
+pub mod pubkey {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(key: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(key)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Pubkey::from_str(&s).map_err(D::Error::custom)
+    }
+}
+
+/// Optional public keys as base58 strings or `null`
+pub mod option_pubkey {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        key: &Option<Pubkey>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match key {
+            Some(key) => serializer.collect_str(key),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Pubkey>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| Pubkey::from_str(&s).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// Lists of public keys as base58 strings
+pub mod pubkeys {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(keys: &[Pubkey], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(keys.iter().map(|k| k.to_string()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Pubkey>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| Pubkey::from_str(s).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+/// Raw bytes as base58 strings
+pub mod base58 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&bs58::encode(bytes).into_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        bs58::decode(s).into_vec().map_err(D::Error::custom)
+    }
+}
+
+/// Raw bytes (ETH addresses, signatures) as `0x` prefixed hex strings
+pub mod hex_bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s.trim_start_matches("0x")).map_err(D::Error::custom)
+    }
+}
+
+/// 32 bytes hashes as `0x` prefixed hex strings
+pub mod hex_array {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        hex_bytes::serialize(bytes, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let bytes = hex_bytes::deserialize(deserializer)?;
+        std::convert::TryInto::try_into(bytes).map_err(|_| D::Error::custom("Expected 32 bytes"))
+    }
+}
+
+/// Record content as `{"utf8": "..."}` when it is valid UTF-8, `{"hex": "..."}` otherwise
+pub mod content {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Content {
+        Utf8(String),
+        Hex(String),
+    }
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        match std::str::from_utf8(bytes) {
+            Ok(s) => Content::Utf8(s.to_owned()),
+            Err(_) => Content::Hex(hex::encode(bytes)),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        match Content::deserialize(deserializer)? {
+            Content::Utf8(s) => Ok(s.into_bytes()),
+            Content::Hex(h) => hex::decode(h.trim_start_matches("0x")).map_err(D::Error::custom),
+        }
+    }
+}
+
+/// Encodes a staleness or RoA id according to its validation type
+pub fn encode_validation_id(validation: Validation, id: &[u8]) -> Option<String> {
+    match validation {
+        Validation::None => None,
+        Validation::Solana | Validation::UnverifiedSolana => Some(bs58::encode(id).into_string()),
+        Validation::Ethereum
+        | Validation::XChain
+        | Validation::Secp256r1
+        | Validation::Cosmos
+        | Validation::Sui
+        | Validation::Aptos
+        | Validation::EthereumAttested => Some(format!("0x{}", hex::encode(id))),
+    }
+}
+
+/// Decodes an id produced by [`encode_validation_id`]
+pub fn decode_validation_id(validation: Validation, id: Option<&str>) -> Result<Vec<u8>, String> {
+    match (validation, id) {
+        (Validation::None, None) => Ok(vec![]),
+        (Validation::None, Some(_)) => Err("Unexpected id for a None validation".to_owned()),
+        (_, None) => Err(format!("Missing id for a {:?} validation", validation)),
+        (Validation::Solana, Some(id)) | (Validation::UnverifiedSolana, Some(id)) => {
+            bs58::decode(id).into_vec().map_err(|e| e.to_string())
+        }
+        (Validation::Ethereum, Some(id))
+        | (Validation::XChain, Some(id))
+        | (Validation::Secp256r1, Some(id))
+        | (Validation::Cosmos, Some(id))
+        | (Validation::Sui, Some(id))
+        | (Validation::Aptos, Some(id))
+        | (Validation::EthereumAttested, Some(id)) => {
+            hex::decode(id.trim_start_matches("0x")).map_err(|e| e.to_string())
+        }
+    }
+}
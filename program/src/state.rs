@@ -1,2 +1,11 @@
+pub mod attestor_set;
+pub mod config;
+pub mod content_encoding;
+pub mod message;
+pub mod off_chain;
+pub mod parent_authority;
+pub mod record;
 pub mod record_header;
+pub mod record_list;
+pub mod transition;
 pub mod validation;
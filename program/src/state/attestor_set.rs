@@ -0,0 +1,74 @@
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+};
+
+use crate::error::SnsRecordsError;
+
+/// The set of attestors allowed to vouch for Ethereum contract wallets (e.g Safe multisigs)
+///
+/// The attestors check off-chain that the contract wallet approved the record message
+/// (EIP-1271 `isValidSignature`) and co-sign the validation transaction. The program can't check
+/// the approval, so the written address is only as trustworthy as `threshold` of the attestors.
+/// The set is managed by the config admin.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[allow(missing_docs)]
+pub struct AttestorSet {
+    /// The number of distinct attestor signatures required
+    pub threshold: u8,
+    pub attestors: Vec<Pubkey>,
+}
+
+impl AttestorSet {
+    pub const SEED: &'static [u8] = b"attestor_set";
+    pub const MAX_ATTESTORS: usize = 16;
+    /// The allocated size, large enough for `MAX_ATTESTORS`
+    pub const LEN: usize = 1 + 4 + 32 * Self::MAX_ATTESTORS;
+
+    pub fn find_key(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED], program_id)
+    }
+
+    /// Checks the threshold and the attestors of a new set
+    pub fn check(&self) -> Result<(), SnsRecordsError> {
+        if self.attestors.len() > Self::MAX_ATTESTORS {
+            return Err(SnsRecordsError::InvalidAttestorSet);
+        }
+        if self.threshold == 0 || self.threshold as usize > self.attestors.len() {
+            return Err(SnsRecordsError::InvalidAttestorSet);
+        }
+        for (i, attestor) in self.attestors.iter().enumerate() {
+            if self.attestors[..i].contains(attestor) {
+                return Err(SnsRecordsError::InvalidAttestorSet);
+            }
+        }
+        Ok(())
+    }
+
+    /// Deserializes the set, `None` if the account was never initialized
+    pub fn from_account_info(account: &AccountInfo) -> Result<Option<Self>, ProgramError> {
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+        let data = account.data.borrow();
+        Ok(Some(Self::deserialize(&mut &data[..])?))
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = account.data.borrow_mut();
+        self.serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+
+    /// Counts the distinct `signers` that belong to the set
+    pub fn count_signers(&self, signers: &[AccountInfo]) -> usize {
+        let mut counted: Vec<&Pubkey> = Vec::with_capacity(signers.len());
+        for signer in signers.iter().filter(|a| a.is_signer) {
+            if self.attestors.contains(signer.key) && !counted.contains(&signer.key) {
+                counted.push(signer.key);
+            }
+        }
+        counted.len()
+    }
+}
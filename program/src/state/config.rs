@@ -0,0 +1,157 @@
+use {
+    bonfida_utils::checks::check_account_owner,
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, program_error::ProgramError, pubkey, pubkey::Pubkey,
+    },
+    std::ops::Deref,
+};
+
+use crate::{
+    error::SnsRecordsError,
+    instruction::ProgramInstruction,
+    record_kind::{max_content_length, MAX_CONTENT_LENGTH},
+    state::validation::Validation,
+};
+
+/// The program configuration, stored in a PDA derived from the central state
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct Config {
+    /// The key allowed to update the configuration
+    pub admin: Pubkey,
+    /// When set, all record mutating instructions are rejected
+    pub paused: bool,
+    /// Bitmask of the paused instructions, indexed by `ProgramInstruction` discriminant
+    pub paused_instructions: u64,
+    /// Bitmask of the allowed RoA validations, indexed by `Validation` discriminant
+    pub enabled_validations: u64,
+    /// The maximum content length of a record, on top of the per record kind limits
+    pub max_content_length: u32,
+}
+
+impl Default for Config {
+    /// The configuration used while the config account is not initialized
+    fn default() -> Self {
+        Self {
+            admin: Pubkey::default(),
+            paused: false,
+            paused_instructions: 0,
+            enabled_validations: u64::MAX,
+            max_content_length: u32::MAX,
+        }
+    }
+}
+
+impl Config {
+    pub const SEED: &'static [u8] = b"config";
+    pub const LEN: usize = 32 + 1 + 8 + 8 + 4;
+    /// The config account, `find_key(&crate::ID)` hardcoded to spare the PDA search on every
+    /// instruction
+    pub const KEY: Pubkey = pubkey!("HA4CfG8RkGYPnJ4QyegV2WzoqmfpYxfRob3iyqJgbp5C");
+    /// The bump seed of `KEY`
+    pub const NONCE: u8 = 254;
+
+    pub fn find_key(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[crate::central_state::KEY.as_ref(), Self::SEED],
+            program_id,
+        )
+    }
+
+    /// Deserializes the config, `None` if the account was never initialized
+    pub fn from_account_info(account: &AccountInfo) -> Result<Option<Self>, ProgramError> {
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+        let data = account.data.borrow();
+        Ok(Some(Self::deserialize(&mut &data[..])?))
+    }
+
+    /// Deserializes the config, falling back to the default one if the account was never initialized
+    pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        Ok(Self::from_account_info(account)?.unwrap_or_default())
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = account.data.borrow_mut();
+        self.serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+
+    /// Looks up the config account among the instruction accounts, wherever it is, and loads it
+    ///
+    /// Falls back to the default config while the account is not initialized.
+    pub fn find_in(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> Result<ConfigAccount, ProgramError> {
+        let key = Self::KEY;
+        let account = accounts
+            .iter()
+            .find(|a| a.key == &key)
+            .ok_or(SnsRecordsError::MissingConfig)?;
+        if !account.data_is_empty() {
+            check_account_owner(account, program_id)?;
+        }
+        Ok(ConfigAccount {
+            key,
+            config: Self::load(account)?,
+        })
+    }
+
+    /// Checks the global pause flag and the per instruction pause bitmask
+    pub fn check_not_paused(&self, instruction: ProgramInstruction) -> Result<(), SnsRecordsError> {
+        if self.paused {
+            return Err(SnsRecordsError::ProgramPaused);
+        }
+        if self.paused_instructions & (1 << instruction as u8) != 0 {
+            return Err(SnsRecordsError::InstructionPaused);
+        }
+        Ok(())
+    }
+
+    pub fn check_validation(&self, validation: Validation) -> Result<(), SnsRecordsError> {
+        if self.enabled_validations & (1 << validation as u16) == 0 {
+            return Err(SnsRecordsError::ValidationDisabled);
+        }
+        Ok(())
+    }
+
+    /// Checks the content length against the config limit and the limit of the record kind
+    pub fn check_content_length(
+        &self,
+        record: &str,
+        content_length: usize,
+    ) -> Result<(), SnsRecordsError> {
+        let max_content_length = self.max_content_length.min(max_content_length(record));
+        if content_length > max_content_length as usize {
+            return Err(SnsRecordsError::ContentTooLarge);
+        }
+        Ok(())
+    }
+
+    /// Checks the total length of a list record, each entry being checked against the limit of
+    /// the record kind
+    pub fn check_list_length(&self, content_length: usize) -> Result<(), SnsRecordsError> {
+        if content_length > self.max_content_length.min(MAX_CONTENT_LENGTH) as usize {
+            return Err(SnsRecordsError::ContentTooLarge);
+        }
+        Ok(())
+    }
+}
+
+/// The config of an instruction, looked up once at dispatch and passed to the processor
+pub struct ConfigAccount {
+    /// The address of the config account
+    pub key: Pubkey,
+    pub config: Config,
+}
+
+impl Deref for ConfigAccount {
+    type Target = Config;
+
+    fn deref(&self) -> &Config {
+        &self.config
+    }
+}
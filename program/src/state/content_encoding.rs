@@ -0,0 +1,143 @@
+//! Encodings of the stored record content
+//!
+//! The encoding is stored in the record header and the content is stored encoded to save rent.
+//! The encoded content is checked when it is written and validation messages commit to the
+//! decoded content, or to the payload hash for off-chain content, so that the same content
+//! compressed differently keeps its RoA.
+
+use std::{
+    borrow::Cow,
+    convert::TryFrom,
+    io::{self, Read},
+};
+
+use {
+    bonfida_utils::BorshSize,
+    borsh::{BorshDeserialize, BorshSerialize},
+    miniz_oxide::inflate::{
+        core::{
+            decompress, inflate_flags::TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF, DecompressorOxide,
+        },
+        TINFLStatus,
+    },
+};
+
+use super::{off_chain::OffChainPointer, record_list};
+use crate::{error::SnsRecordsError, record_kind::MAX_CONTENT_LENGTH};
+
+/// The maximum length of a decoded content, bounds the memory used to decode records on-chain
+pub const MAX_DECODED_LENGTH: usize = MAX_CONTENT_LENGTH as usize;
+
+/// The initial output buffer of the inflation, grown as needed up to `MAX_DECODED_LENGTH`
+const MIN_INFLATE_BUFFER: usize = 256;
+
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSize, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[repr(u8)]
+pub enum ContentEncoding {
+    /// The content is stored as is
+    #[default]
+    None,
+    /// The content is a raw DEFLATE stream (RFC 1951)
+    Deflate,
+    /// The content is an `OffChainPointer` to a payload stored off-chain
+    OffChain,
+    /// The content is a list of entries, see `record_list`
+    List,
+}
+
+impl TryFrom<u8> for ContentEncoding {
+    type Error = SnsRecordsError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ContentEncoding::None),
+            1 => Ok(ContentEncoding::Deflate),
+            2 => Ok(ContentEncoding::OffChain),
+            3 => Ok(ContentEncoding::List),
+            _ => Err(SnsRecordsError::InvalidContentEncoding),
+        }
+    }
+}
+
+impl ContentEncoding {
+    /// Deserializes an encoding ending the instruction params, `None` when it is left out by
+    /// clients predating content encodings
+    pub fn deserialize_trailing<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut tag = [0; 1];
+        if reader.read(&mut tag)? == 0 {
+            return Ok(Self::None);
+        }
+        Self::deserialize(&mut &tag[..])
+    }
+}
+
+/// Encodes `content` to be stored in a record
+pub fn encode(encoding: ContentEncoding, content: &[u8]) -> Vec<u8> {
+    match encoding {
+        ContentEncoding::None | ContentEncoding::OffChain | ContentEncoding::List => {
+            content.to_vec()
+        }
+        ContentEncoding::Deflate => miniz_oxide::deflate::compress_to_vec(content, 10),
+    }
+}
+
+/// Checks that the stored `content` of a record is validly encoded, without keeping the decoded
+/// content
+pub fn validate(encoding: ContentEncoding, content: &[u8]) -> Result<(), SnsRecordsError> {
+    decode(encoding, content).map(|_| ())
+}
+
+/// Inflates a raw DEFLATE stream decoding to at most `MAX_DECODED_LENGTH` bytes
+///
+/// The output buffer starts at twice the stream length and doubles as needed, so that short
+/// records don't pay for the maximum length. A stream followed by trailing bytes is rejected.
+fn inflate(content: &[u8]) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut decompressor = Box::<DecompressorOxide>::default();
+    let initial_length = content
+        .len()
+        .saturating_mul(2)
+        .clamp(MIN_INFLATE_BUFFER, MAX_DECODED_LENGTH);
+    let mut output = vec![0; initial_length];
+    let (mut in_pos, mut out_pos) = (0, 0);
+    loop {
+        let (status, consumed, written) = decompress(
+            &mut decompressor,
+            &content[in_pos..],
+            &mut output,
+            out_pos,
+            TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF,
+        );
+        in_pos += consumed;
+        out_pos += written;
+        match status {
+            TINFLStatus::Done if in_pos == content.len() => {
+                output.truncate(out_pos);
+                return Ok(output);
+            }
+            TINFLStatus::HasMoreOutput if output.len() < MAX_DECODED_LENGTH => {
+                let length = output.len().saturating_mul(2).min(MAX_DECODED_LENGTH);
+                output.resize(length, 0);
+            }
+            _ => return Err(SnsRecordsError::InvalidContentEncoding),
+        }
+    }
+}
+
+/// Decodes the stored `content` of a record
+pub fn decode(encoding: ContentEncoding, content: &[u8]) -> Result<Cow<'_, [u8]>, SnsRecordsError> {
+    match encoding {
+        ContentEncoding::None => Ok(Cow::Borrowed(content)),
+        ContentEncoding::Deflate => inflate(content).map(Cow::Owned),
+        ContentEncoding::OffChain => {
+            OffChainPointer::parse(content)?;
+            Ok(Cow::Borrowed(content))
+        }
+        ContentEncoding::List => {
+            record_list::parse_entries(content)?;
+            Ok(Cow::Borrowed(content))
+        }
+    }
+}
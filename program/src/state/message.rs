@@ -0,0 +1,125 @@
+//! Messages signed to validate the RoA of a record
+//!
+//! They all commit to the record content, the record key and the staleness id so that a
+//! signature can't be replayed on another record or after the domain is transferred.
+
+use solana_program::{
+    hash::{hash, hashv},
+    pubkey::Pubkey,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::crypto::blake2b256;
+
+pub const ETH_PREFIX_BYTES: &[u8; 26] = b"\x19Ethereum Signed Message:\n";
+pub const RECORD_SUFFIX: &[u8; 13] = b"\nFor record: ";
+pub const STALENESS_SUFFIX: &[u8; 15] = b"\nStaleness ID: ";
+
+///
+/// The message to sign must contain the record public key & staleness ID
+// +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
+// | ETH_PREFIX_BYTES       | content_length   | content          | RECORD_SUFFIX    | record_key       | STALENESS_SUFFIX | staleness_id     |
+// +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
+// | "\x19Ethereum Signed   | Length of        | Actual content   | "\nFor record: " | Public key of    | "\nStaleness ID: " | Public key of   |
+// | Message:\n"            | (content +       | to be signed     |                  | the record       |                  | the staleness    |
+// |                        | record_key +     |                  |                  |                  |                  |                  |
+// |                        | RECORD_SUFFIX +  |                  |                  |                  |                  |                  |
+// |                        | staleness_id +   |                  |                  |                  |                  |                  |
+// |                        | STALENESS_SUFFIX)|                  |                  |                  |                  |                  |
+// +------------------------+------------------+------------------+------------------+------------------+------------------+------------------+
+pub fn message_to_sign(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
+    let message = record_message(content, record_key, staleness_id);
+
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(ETH_PREFIX_BYTES);
+    buffer.extend_from_slice(message.len().to_string().as_bytes());
+    buffer.extend_from_slice(&message);
+
+    buffer
+}
+
+/// The canonical record message, without any chain specific prefix
+///
+/// `hex(content) | RECORD_SUFFIX | record_key | STALENESS_SUFFIX | staleness_id` with keys in
+/// base58
+pub fn record_message(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(hex::encode(content).as_bytes());
+    buffer.extend_from_slice(RECORD_SUFFIX);
+    buffer.extend_from_slice(record_key.to_string().as_bytes());
+    buffer.extend_from_slice(STALENESS_SUFFIX);
+    buffer.extend_from_slice(staleness_id.to_string().as_bytes());
+    buffer
+}
+
+/// The WebAuthn relying party of the passkeys validating RoAs, its SHA-256 must lead the
+/// authenticator data of the assertions
+pub const WEBAUTHN_RP_ID: &str = "sns.id";
+
+/// The WebAuthn challenge binding a record key, its staleness id and its content
+///
+/// The challenge is the SHA-256 of `content | record_key | staleness_id`, it must be passed to
+/// `navigator.credentials.get` as is and appears base64url encoded in the `clientDataJSON`.
+pub fn webauthn_challenge(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> [u8; 32] {
+    hashv(&[content, record_key.as_ref(), staleness_id.as_ref()]).to_bytes()
+}
+
+/// The message signed by the authenticator: `authenticator_data | sha256(client_data_json)`
+///
+/// This is the message the secp256r1 precompile instruction must verify.
+pub fn webauthn_signed_message(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+    let mut message = authenticator_data.to_vec();
+    message.extend_from_slice(hash(client_data_json).as_ref());
+    message
+}
+
+/// The amino JSON sign doc of an ADR-36 `signArbitrary` signature, with sorted keys and no
+/// whitespace
+pub fn adr36_sign_doc(signer: &str, data: &[u8]) -> Vec<u8> {
+    format!(
+        concat!(
+            r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","#,
+            r#""msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"#,
+            r#""sequence":"0"}}"#
+        ),
+        STANDARD.encode(data),
+        signer
+    )
+    .into_bytes()
+}
+
+/// The digest signed by a Sui wallet `signPersonalMessage` of the record message
+///
+/// `blake2b256(intent | bcs(message))` with the `PersonalMessage` intent `[3, 0, 0]`
+pub fn sui_signed_message(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
+    let message = record_message(content, record_key, staleness_id);
+    let mut intent_message = vec![3, 0, 0];
+    // BCS encodes the length of a byte vector as ULEB128
+    let mut length = message.len();
+    loop {
+        let byte = (length & 0x7f) as u8;
+        length >>= 7;
+        if length == 0 {
+            intent_message.push(byte);
+            break;
+        }
+        intent_message.push(byte | 0x80);
+    }
+    intent_message.extend_from_slice(&message);
+    blake2b256(&intent_message).to_vec()
+}
+
+/// The full message signed by an Aptos wallet `signMessage` of the record message, with the
+/// staleness id as nonce
+///
+/// `APTOS\nmessage: <message>\nnonce: <staleness_id>`, the address, application and chain id
+/// must not be requested from the wallet.
+pub fn aptos_signed_message(content: &[u8], record_key: &Pubkey, staleness_id: &Pubkey) -> Vec<u8> {
+    let message = record_message(content, record_key, staleness_id);
+    let mut full_message = b"APTOS\nmessage: ".to_vec();
+    full_message.extend_from_slice(&message);
+    full_message.extend_from_slice(b"\nnonce: ");
+    full_message.extend_from_slice(staleness_id.to_string().as_bytes());
+    full_message
+}
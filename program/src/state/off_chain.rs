@@ -0,0 +1,143 @@
+//! Pointers to record payloads stored off-chain
+//!
+//! A record stored with `ContentEncoding::OffChain` holds a typed pointer to its payload along
+//! with the sha256 of the payload, validation messages commit to that hash.
+//!
+//! +------+-------------+----------+
+//! | kind | hash (32 B) | location |
+//! +------+-------------+----------+
+
+use std::convert::{TryFrom, TryInto};
+
+use solana_program::hash::hash;
+
+use crate::error::SnsRecordsError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[repr(u8)]
+pub enum PointerKind {
+    /// The location is an IPFS CID
+    Ipfs,
+    /// The location is an Arweave transaction id
+    Arweave,
+    /// The location is an HTTPS URL
+    Https,
+}
+
+impl TryFrom<u8> for PointerKind {
+    type Error = SnsRecordsError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PointerKind::Ipfs),
+            1 => Ok(PointerKind::Arweave),
+            2 => Ok(PointerKind::Https),
+            _ => Err(SnsRecordsError::InvalidOffChainPointer),
+        }
+    }
+}
+
+impl PointerKind {
+    fn check_location(self, location: &str) -> Result<(), SnsRecordsError> {
+        let valid = match self {
+            PointerKind::Ipfs => {
+                !location.is_empty() && location.bytes().all(|b| b.is_ascii_alphanumeric())
+            }
+            PointerKind::Arweave => {
+                location.len() == 43
+                    && location
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+            }
+            PointerKind::Https => location.len() > 8 && location.starts_with("https://"),
+        };
+        if !valid {
+            return Err(SnsRecordsError::InvalidOffChainPointer);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OffChainPointer {
+    pub kind: PointerKind,
+    /// The sha256 of the off-chain payload
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_array"))]
+    pub hash: [u8; 32],
+    pub location: String,
+}
+
+impl OffChainPointer {
+    pub const HASH_OFFSET: usize = 1;
+    const LOCATION_OFFSET: usize = Self::HASH_OFFSET + 32;
+
+    pub fn new(
+        kind: PointerKind,
+        payload: &[u8],
+        location: String,
+    ) -> Result<Self, SnsRecordsError> {
+        kind.check_location(&location)?;
+        Ok(Self {
+            kind,
+            hash: hash(payload).to_bytes(),
+            location,
+        })
+    }
+
+    /// Parses a pointer from the stored content of a record
+    pub fn parse(content: &[u8]) -> Result<Self, SnsRecordsError> {
+        if content.len() < Self::LOCATION_OFFSET {
+            return Err(SnsRecordsError::InvalidOffChainPointer);
+        }
+        let kind = PointerKind::try_from(content[0])?;
+        let location = std::str::from_utf8(&content[Self::LOCATION_OFFSET..])
+            .map_err(|_| SnsRecordsError::InvalidOffChainPointer)?;
+        kind.check_location(location)?;
+        Ok(Self {
+            kind,
+            hash: content[Self::HASH_OFFSET..Self::LOCATION_OFFSET]
+                .try_into()
+                .unwrap(),
+            location: location.to_owned(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut content = Vec::with_capacity(Self::LOCATION_OFFSET + self.location.len());
+        content.push(self.kind as u8);
+        content.extend_from_slice(&self.hash);
+        content.extend_from_slice(self.location.as_bytes());
+        content
+    }
+
+    /// Parses an `ipfs://`, `ar://` or `https://` URI
+    pub fn from_uri(uri: &str, payload: &[u8]) -> Result<Self, SnsRecordsError> {
+        let (kind, location) = if let Some(cid) = uri.strip_prefix("ipfs://") {
+            (PointerKind::Ipfs, cid)
+        } else if let Some(id) = uri.strip_prefix("ar://") {
+            (PointerKind::Arweave, id)
+        } else {
+            (PointerKind::Https, uri)
+        };
+        Self::new(kind, payload, location.to_owned())
+    }
+
+    pub fn uri(&self) -> String {
+        match self.kind {
+            PointerKind::Ipfs => format!("ipfs://{}", self.location),
+            PointerKind::Arweave => format!("ar://{}", self.location),
+            PointerKind::Https => self.location.clone(),
+        }
+    }
+
+    /// Checks a downloaded payload against the committed hash
+    pub fn verify_payload(&self, payload: &[u8]) -> Result<(), SnsRecordsError> {
+        if hash(payload).to_bytes() != self.hash {
+            return Err(SnsRecordsError::ContentHashMismatch);
+        }
+        Ok(())
+    }
+}
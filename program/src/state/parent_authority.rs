@@ -0,0 +1,88 @@
+use {
+    bonfida_utils::checks::{check_account_key, check_account_owner},
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::AccountInfo, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
+    },
+    spl_name_service::state::NameRecordHeader,
+};
+
+use crate::{error::SnsRecordsError, utils::check_domain_owner_or_holder};
+
+/// The opt-in of a subdomain letting the owner of its parent domain manage its records, stored in
+/// a PDA derived from the subdomain
+///
+/// The opt-in only holds while the subdomain is owned by the key that signed it, it lapses when
+/// the subdomain is transferred. For a tokenized subdomain, owned by the vault of the name
+/// tokenizer, the opt-in holds until the domain is redeemed and carries over transfers of the NFT,
+/// so a holder should disable it before selling the NFT.
+///
+/// Records are still allocated by the subdomain owner, since the name service requires the owner
+/// of the subdomain to sign the creation of a record account.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct ParentAuthority {
+    /// The subdomain owner who opted in, the default key when disabled
+    pub owner: Pubkey,
+}
+
+impl ParentAuthority {
+    pub const SEED: &'static [u8] = b"parent_authority";
+    pub const LEN: usize = 32;
+
+    pub fn find_key(domain: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED, domain.as_ref()], program_id)
+    }
+
+    /// Deserializes the opt-in, `None` if the account was never initialized
+    pub fn from_account_info(account: &AccountInfo) -> Result<Option<Self>, ProgramError> {
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+        let data = account.data.borrow();
+        Ok(Some(Self::deserialize(&mut &data[..])?))
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = account.data.borrow_mut();
+        self.serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+}
+
+/// Checks that `authority` may manage the records of `domain`
+///
+/// `authority` must be the domain owner, or the owner of `parent_domain` when the accounts of the
+/// parent authority mode are provided and the current domain owner opted in. In token-gated mode,
+/// `domain_token_account` holds the NFT of the domain whose owner is checked.
+pub fn check_domain_authority(
+    program_id: &Pubkey,
+    domain: &AccountInfo,
+    authority: &Pubkey,
+    domain_token_account: Option<&AccountInfo>,
+    parent_domain: Option<&AccountInfo>,
+    parent_authority: Option<&AccountInfo>,
+) -> Result<(), ProgramError> {
+    let (parent_domain, parent_authority) = match (parent_domain, parent_authority) {
+        (Some(parent_domain), Some(parent_authority)) => (parent_domain, parent_authority),
+        _ => return check_domain_owner_or_holder(domain, authority, domain_token_account),
+    };
+
+    let header = NameRecordHeader::unpack_from_slice(&domain.data.borrow())
+        .map_err(|_| SnsRecordsError::DataTypeMismatch)?;
+    if header.parent_name != *parent_domain.key {
+        return Err(SnsRecordsError::WrongParent.into());
+    }
+    check_account_owner(parent_domain, &spl_name_service::ID)?;
+    check_domain_owner_or_holder(parent_domain, authority, domain_token_account)?;
+
+    check_account_key(
+        parent_authority,
+        &ParentAuthority::find_key(domain.key, program_id).0,
+    )?;
+    check_account_owner(parent_authority, program_id)?;
+    match ParentAuthority::from_account_info(parent_authority)? {
+        Some(opt_in) if opt_in.owner == header.owner => Ok(()),
+        _ => Err(SnsRecordsError::ParentAuthorityDisabled.into()),
+    }
+}
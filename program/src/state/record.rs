@@ -0,0 +1,246 @@
+use std::{
+    borrow::Cow,
+    convert::{TryFrom, TryInto},
+};
+
+use solana_program::program_pack::Pack;
+use spl_name_service::state::NameRecordHeader;
+
+use crate::error::SnsRecordsError;
+
+use super::{
+    content_encoding::{self, ContentEncoding},
+    off_chain::OffChainPointer,
+    record_header::RecordHeader,
+    record_list::{self, RecordEntry},
+    validation::{get_validation_length, Validation},
+};
+
+/// Borrowed view over the data of a record account, starting right after the `NameRecordHeader`
+///
+/// +--------------+--------------+--------+---------+
+/// | RecordHeader | staleness_id | roa_id | content |
+/// +--------------+--------------+--------+---------+
+#[derive(Clone, Copy, Debug)]
+#[allow(missing_docs)]
+pub struct RecordView<'a> {
+    pub header: RecordHeader,
+    pub staleness_id: &'a [u8],
+    pub roa_id: &'a [u8],
+    pub content: &'a [u8],
+}
+
+impl<'a> RecordView<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, SnsRecordsError> {
+        let header_bytes = data
+            .get(..RecordHeader::LEN)
+            .ok_or(SnsRecordsError::OutOfBound)?;
+        let header = bytemuck::pod_read_unaligned::<RecordHeader>(header_bytes);
+        let buffer = &data[RecordHeader::LEN..];
+
+        let staleness_length = get_validation_length(header.staleness_validation.try_into()?);
+        let (staleness_id, rest) = split_at_checked(buffer, staleness_length as usize)?;
+
+        let roa_length = get_validation_length(header.right_of_association_validation.try_into()?);
+        let (roa_id, content) = split_at_checked(rest, roa_length as usize)?;
+
+        Ok(Self {
+            header,
+            staleness_id,
+            roa_id,
+            content,
+        })
+    }
+
+    pub fn staleness_validation(&self) -> Result<Validation, SnsRecordsError> {
+        self.header.staleness_validation.try_into()
+    }
+
+    pub fn right_of_association_validation(&self) -> Result<Validation, SnsRecordsError> {
+        self.header.right_of_association_validation.try_into()
+    }
+
+    pub fn content_encoding(&self) -> Result<ContentEncoding, SnsRecordsError> {
+        self.header.content_encoding()
+    }
+
+    /// The decoded content, DEFLATE streams are inflated
+    pub fn decoded_content(&self) -> Result<Cow<'a, [u8]>, SnsRecordsError> {
+        content_encoding::decode(self.content_encoding()?, self.content)
+    }
+
+    /// The content validation messages commit to: the payload hash for off-chain content, the
+    /// decoded content otherwise
+    pub fn committed_content(&self) -> Result<Cow<'a, [u8]>, SnsRecordsError> {
+        match self.content_encoding()? {
+            ContentEncoding::OffChain => self
+                .content
+                .get(OffChainPointer::HASH_OFFSET..OffChainPointer::HASH_OFFSET + 32)
+                .map(Cow::Borrowed)
+                .ok_or(SnsRecordsError::InvalidOffChainPointer),
+            _ => self.decoded_content(),
+        }
+    }
+
+    /// The length of the serialized record, header included
+    pub fn serialized_len(&self) -> Result<usize, SnsRecordsError> {
+        RecordHeader::LEN
+            .checked_add(self.staleness_id.len())
+            .and_then(|l| l.checked_add(self.roa_id.len()))
+            .and_then(|l| l.checked_add(self.content.len()))
+            .ok_or(SnsRecordsError::NumericalOverflow)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SnsRecordsError> {
+        let mut data = Vec::with_capacity(self.serialized_len()?);
+        data.extend_from_slice(bytemuck::bytes_of(&self.header));
+        data.extend_from_slice(self.staleness_id);
+        data.extend_from_slice(self.roa_id);
+        data.extend_from_slice(self.content);
+        Ok(data)
+    }
+}
+
+pub(crate) fn split_at_checked(
+    buffer: &[u8],
+    mid: usize,
+) -> Result<(&[u8], &[u8]), SnsRecordsError> {
+    if mid > buffer.len() {
+        return Err(SnsRecordsError::OutOfBound);
+    }
+    Ok(buffer.split_at(mid))
+}
+
+/// Owned and decoded version of a record account
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        into = "serde_impl::DecodedRecordSerde",
+        try_from = "serde_impl::DecodedRecordSerde"
+    )
+)]
+#[allow(missing_docs)]
+pub struct DecodedRecord {
+    pub staleness_validation: Validation,
+    pub right_of_association_validation: Validation,
+    pub staleness_id: Vec<u8>,
+    pub roa_id: Vec<u8>,
+    /// How the content is stored in the record
+    pub content_encoding: ContentEncoding,
+    /// The content, decoded according to the content encoding of the record
+    pub content: Vec<u8>,
+}
+
+impl DecodedRecord {
+    /// Decodes the data of a record account, `NameRecordHeader` included
+    pub fn from_account_data(data: &[u8]) -> Result<Self, SnsRecordsError> {
+        let data = data
+            .get(NameRecordHeader::LEN..)
+            .ok_or(SnsRecordsError::OutOfBound)?;
+        Self::try_from(RecordView::parse(data)?)
+    }
+
+    /// The pointer to the payload, `None` unless the content is stored off-chain
+    pub fn off_chain_pointer(&self) -> Result<Option<OffChainPointer>, SnsRecordsError> {
+        if self.content_encoding != ContentEncoding::OffChain {
+            return Ok(None);
+        }
+        OffChainPointer::parse(&self.content).map(Some)
+    }
+
+    /// The entries of a list record, `None` unless the content is a list
+    pub fn entries(&self) -> Result<Option<Vec<RecordEntry>>, SnsRecordsError> {
+        if self.content_encoding != ContentEncoding::List {
+            return Ok(None);
+        }
+        record_list::parse_entries(&self.content).map(Some)
+    }
+
+    /// Checks a downloaded off-chain payload against the hash committed in the record
+    pub fn verify_payload(&self, payload: &[u8]) -> Result<(), SnsRecordsError> {
+        self.off_chain_pointer()?
+            .ok_or(SnsRecordsError::InvalidContentEncoding)?
+            .verify_payload(payload)
+    }
+}
+
+impl<'a> TryFrom<RecordView<'a>> for DecodedRecord {
+    type Error = SnsRecordsError;
+
+    fn try_from(view: RecordView<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            staleness_validation: view.staleness_validation()?,
+            right_of_association_validation: view.right_of_association_validation()?,
+            staleness_id: view.staleness_id.to_vec(),
+            roa_id: view.roa_id.to_vec(),
+            content_encoding: view.content_encoding()?,
+            content: view.decoded_content()?.into_owned(),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::{Deserialize, Serialize};
+
+    use super::DecodedRecord;
+    use crate::{
+        serde_helpers::{decode_validation_id, encode_validation_id},
+        state::{content_encoding::ContentEncoding, validation::Validation},
+    };
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DecodedRecordSerde {
+        staleness_validation: Validation,
+        staleness_id: Option<String>,
+        right_of_association_validation: Validation,
+        roa_id: Option<String>,
+        #[serde(default)]
+        content_encoding: ContentEncoding,
+        #[serde(with = "crate::serde_helpers::content")]
+        content: Vec<u8>,
+    }
+
+    impl From<DecodedRecord> for DecodedRecordSerde {
+        fn from(record: DecodedRecord) -> Self {
+            Self {
+                staleness_id: encode_validation_id(
+                    record.staleness_validation,
+                    &record.staleness_id,
+                ),
+                roa_id: encode_validation_id(
+                    record.right_of_association_validation,
+                    &record.roa_id,
+                ),
+                staleness_validation: record.staleness_validation,
+                right_of_association_validation: record.right_of_association_validation,
+                content_encoding: record.content_encoding,
+                content: record.content,
+            }
+        }
+    }
+
+    impl std::convert::TryFrom<DecodedRecordSerde> for DecodedRecord {
+        type Error = String;
+
+        fn try_from(record: DecodedRecordSerde) -> Result<Self, Self::Error> {
+            Ok(Self {
+                staleness_id: decode_validation_id(
+                    record.staleness_validation,
+                    record.staleness_id.as_deref(),
+                )?,
+                roa_id: decode_validation_id(
+                    record.right_of_association_validation,
+                    record.roa_id.as_deref(),
+                )?,
+                staleness_validation: record.staleness_validation,
+                right_of_association_validation: record.right_of_association_validation,
+                content_encoding: record.content_encoding,
+                content: record.content,
+            })
+        }
+    }
+}
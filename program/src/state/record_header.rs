@@ -1,21 +1,46 @@
 use solana_program::program_pack::Pack;
 use spl_name_service::state::NameRecordHeader;
 
-use super::validation::Validation;
+use std::convert::TryFrom;
+
+use super::{content_encoding::ContentEncoding, validation::Validation};
 
 use bytemuck::{Pod, Zeroable};
+use enumflags2::{bitflags, BitFlags};
+
+use crate::error::SnsRecordsError;
+
+/// Record flags, stored in bits 24 to 27 of `RecordHeader::content_length`
+#[bitflags]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordFlag {
+    /// The content is being uploaded in chunks and is followed by the hash of the final content
+    Incomplete = 1 << 0,
+}
 
 #[derive(Clone, Copy, Zeroable, Pod, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[allow(missing_docs)]
 #[repr(C)]
 pub struct RecordHeader {
     pub staleness_validation: u16,
     pub right_of_association_validation: u16,
+    /// The content length in the low 24 bits, the record flags in bits 24 to 27 and the content
+    /// encoding in bits 28 to 31
     pub content_length: u32,
 }
 
 impl RecordHeader {
     pub const LEN: usize = std::mem::size_of::<Self>();
+    /// The bits of `content_length` holding the actual length, the others hold the flags and
+    /// the content encoding
+    pub const CONTENT_LENGTH_MASK: u32 = 0x00ff_ffff;
+    const FLAGS_SHIFT: u32 = 24;
+    const FLAGS_MASK: u32 = 0x0f << Self::FLAGS_SHIFT;
+    const ENCODING_SHIFT: u32 = 28;
 
     pub fn from_buffer(buffer: &[u8]) -> Self {
         let offset = NameRecordHeader::LEN;
@@ -30,4 +55,38 @@ impl RecordHeader {
             content_length,
         }
     }
+
+    pub fn content_length(&self) -> u32 {
+        self.content_length & Self::CONTENT_LENGTH_MASK
+    }
+
+    /// Updates the length while keeping the flags and the content encoding
+    pub fn set_content_length(&mut self, content_length: u32) {
+        self.content_length = (self.content_length & !Self::CONTENT_LENGTH_MASK)
+            | (content_length & Self::CONTENT_LENGTH_MASK);
+    }
+
+    pub fn flags(&self) -> BitFlags<RecordFlag> {
+        BitFlags::from_bits_truncate(
+            ((self.content_length & Self::FLAGS_MASK) >> Self::FLAGS_SHIFT) as u8,
+        )
+    }
+
+    pub fn set_flags(&mut self, flags: BitFlags<RecordFlag>) {
+        self.content_length = (self.content_length & !Self::FLAGS_MASK)
+            | (u32::from(flags.bits()) << Self::FLAGS_SHIFT);
+    }
+
+    pub fn content_encoding(&self) -> Result<ContentEncoding, SnsRecordsError> {
+        ContentEncoding::try_from((self.content_length >> Self::ENCODING_SHIFT) as u8)
+    }
+
+    pub fn set_content_encoding(&mut self, encoding: ContentEncoding) {
+        self.content_length = (self.content_length & !(0x0f << Self::ENCODING_SHIFT))
+            | ((encoding as u32) << Self::ENCODING_SHIFT);
+    }
+
+    pub fn is_incomplete(&self) -> bool {
+        self.flags().contains(RecordFlag::Incomplete)
+    }
 }
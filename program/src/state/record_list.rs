@@ -0,0 +1,105 @@
+//! Multi-value record content
+//!
+//! A record stored with `ContentEncoding::List` holds a list of entries, each entry carrying its
+//! own RoA so that several addresses can be verified under the same record name. Entries may hold
+//! addresses of different chains, a list record then bundles the wallets of a user with one
+//! validation slot per wallet.
+//!
+//! +------------+----------------+--------+---------+-----
+//! | validation | content_length | roa_id | content | ...
+//! | (u16)      | (u16)          |        |         |
+//! +------------+----------------+--------+---------+-----
+
+use std::convert::{TryFrom, TryInto};
+
+use crate::error::SnsRecordsError;
+
+use super::{
+    record::split_at_checked,
+    validation::{get_validation_length, Validation},
+};
+
+/// The maximum number of entries of a list record
+pub const MAX_ENTRIES: usize = 32;
+
+/// A single value of a list record
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct RecordEntry {
+    pub right_of_association_validation: Validation,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
+    pub roa_id: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::content"))]
+    pub content: Vec<u8>,
+}
+
+impl RecordEntry {
+    const HEADER_LEN: usize = 4;
+
+    pub fn new(content: Vec<u8>) -> Self {
+        Self {
+            right_of_association_validation: Validation::None,
+            roa_id: vec![],
+            content,
+        }
+    }
+
+    pub fn serialized_len(&self) -> usize {
+        Self::HEADER_LEN + self.roa_id.len() + self.content.len()
+    }
+}
+
+/// Parses the entries of a list record content
+pub fn parse_entries(mut content: &[u8]) -> Result<Vec<RecordEntry>, SnsRecordsError> {
+    let mut entries = Vec::new();
+    while !content.is_empty() {
+        if entries.len() == MAX_ENTRIES {
+            return Err(SnsRecordsError::TooManyEntries);
+        }
+        let (header, rest) = split_at_checked(content, RecordEntry::HEADER_LEN)?;
+        let validation = Validation::try_from(u16::from_le_bytes(header[..2].try_into().unwrap()))?;
+        let content_length = u16::from_le_bytes(header[2..].try_into().unwrap());
+
+        let (roa_id, rest) = split_at_checked(rest, get_validation_length(validation) as usize)?;
+        let (entry, rest) = split_at_checked(rest, content_length as usize)?;
+        entries.push(RecordEntry {
+            right_of_association_validation: validation,
+            roa_id: roa_id.to_vec(),
+            content: entry.to_vec(),
+        });
+        content = rest;
+    }
+    Ok(entries)
+}
+
+/// Checks that a list content written by the domain owner does not claim any entry RoA
+pub fn check_unverified(content: &[u8]) -> Result<(), SnsRecordsError> {
+    if parse_entries(content)?
+        .iter()
+        .any(|e| e.right_of_association_validation != Validation::None)
+    {
+        return Err(SnsRecordsError::UnsupportedValidation);
+    }
+    Ok(())
+}
+
+/// Serializes entries into a list record content
+pub fn serialize_entries(entries: &[RecordEntry]) -> Result<Vec<u8>, SnsRecordsError> {
+    if entries.len() > MAX_ENTRIES {
+        return Err(SnsRecordsError::TooManyEntries);
+    }
+    let mut content = Vec::with_capacity(entries.iter().map(RecordEntry::serialized_len).sum());
+    for entry in entries {
+        let content_length: u16 = entry
+            .content
+            .len()
+            .try_into()
+            .map_err(|_| SnsRecordsError::ContentTooLarge)?;
+        content.extend_from_slice(&(entry.right_of_association_validation as u16).to_le_bytes());
+        content.extend_from_slice(&content_length.to_le_bytes());
+        content.extend_from_slice(&entry.roa_id);
+        content.extend_from_slice(&entry.content);
+    }
+    Ok(content)
+}
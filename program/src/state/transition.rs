@@ -0,0 +1,665 @@
+//! Pure state transitions of a record account
+//!
+//! Every function takes the current record data (starting right after the `NameRecordHeader`)
+//! and returns the full serialized record that must be written back at offset 0. They do not
+//! perform any account check or CPI so they can be used off-chain to simulate instructions.
+
+use std::{convert::TryInto, ops::Range};
+
+use {
+    base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine},
+    sha3::{Digest, Sha3_256},
+    solana_program::{
+        hash::hash, keccak::Hasher, pubkey::Pubkey, secp256k1_recover::secp256k1_recover,
+    },
+};
+
+use crate::{
+    crypto::{bech32_decode, blake2b256, ripemd160},
+    error::SnsRecordsError,
+};
+
+use super::{
+    content_encoding::{self, ContentEncoding},
+    message::{
+        adr36_sign_doc, message_to_sign, record_message, webauthn_challenge, WEBAUTHN_RP_ID,
+    },
+    record::{split_at_checked, RecordView},
+    record_header::{RecordFlag, RecordHeader},
+    record_list::{self, RecordEntry},
+    validation::{get_validation_length, Validation},
+};
+
+/// Serializes a fresh record with no staleness and no RoA, `content` must already be encoded
+/// with `encoding`
+pub fn new_record(content: &[u8], encoding: ContentEncoding) -> Result<Vec<u8>, SnsRecordsError> {
+    content_encoding::validate(encoding, content)?;
+    if encoding == ContentEncoding::List {
+        record_list::check_unverified(content)?;
+    }
+    fresh_record(content, encoding)
+}
+
+fn fresh_record(content: &[u8], encoding: ContentEncoding) -> Result<Vec<u8>, SnsRecordsError> {
+    let content_length = checked_content_length(content.len())?;
+    let mut header = RecordHeader::new(content_length);
+    header.set_content_encoding(encoding);
+    let mut data = Vec::with_capacity(
+        RecordHeader::LEN
+            .checked_add(content.len())
+            .ok_or(SnsRecordsError::NumericalOverflow)?,
+    );
+    data.extend_from_slice(bytemuck::bytes_of(&header));
+    data.extend_from_slice(content);
+    Ok(data)
+}
+
+/// Serializes an incomplete record of `content_length` zeroed bytes, followed by the SHA256 of
+/// the final content
+pub fn new_chunked_record(
+    content_length: u32,
+    content_hash: &[u8],
+    encoding: ContentEncoding,
+) -> Result<Vec<u8>, SnsRecordsError> {
+    if content_hash.len() != 32 {
+        return Err(SnsRecordsError::OutOfBound);
+    }
+    let content_length = checked_content_length(content_length as usize)?;
+    let mut header = RecordHeader::new(content_length);
+    header.set_flags(RecordFlag::Incomplete.into());
+    header.set_content_encoding(encoding);
+
+    let hash_offset = RecordHeader::LEN
+        .checked_add(content_length as usize)
+        .ok_or(SnsRecordsError::NumericalOverflow)?;
+    let mut data = Vec::with_capacity(
+        hash_offset
+            .checked_add(32)
+            .ok_or(SnsRecordsError::NumericalOverflow)?,
+    );
+    data.extend_from_slice(bytemuck::bytes_of(&header));
+    data.resize(hash_offset, 0);
+    data.extend_from_slice(content_hash);
+    Ok(data)
+}
+
+/// Writes `chunk` at `offset` in the content of an incomplete record
+///
+/// The RoA is removed since it could only have been validated over a previous content
+pub fn write_chunk(data: &[u8], offset: u32, chunk: &[u8]) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = RecordView::parse(data)?;
+    if !record.header.is_incomplete() {
+        return Err(SnsRecordsError::UploadNotInProgress);
+    }
+    record.header.right_of_association_validation = Validation::None as u16;
+    record.roa_id = &[];
+    let end = (offset as usize)
+        .checked_add(chunk.len())
+        .ok_or(SnsRecordsError::NumericalOverflow)?;
+    if end > record.header.content_length() as usize {
+        return Err(SnsRecordsError::OutOfBound);
+    }
+
+    let start = record
+        .serialized_len()?
+        .checked_sub(record.content.len())
+        .and_then(|l| l.checked_add(offset as usize))
+        .ok_or(SnsRecordsError::NumericalOverflow)?;
+    let mut data = record.to_bytes()?;
+    data[start..start + chunk.len()].copy_from_slice(chunk);
+    Ok(data)
+}
+
+/// Checks that the content of an incomplete record matches the hash declared at allocation,
+/// then drops the hash and clears the incomplete flag
+pub fn finalize_record(data: &[u8]) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = RecordView::parse(data)?;
+    if !record.header.is_incomplete() {
+        return Err(SnsRecordsError::UploadNotInProgress);
+    }
+    let (content, content_hash) =
+        split_at_checked(record.content, record.header.content_length() as usize)?;
+    if hash(content).as_ref() != content_hash {
+        return Err(SnsRecordsError::ContentHashMismatch);
+    }
+    content_encoding::validate(record.content_encoding()?, content)?;
+    if record.content_encoding()? == ContentEncoding::List {
+        record_list::check_unverified(content)?;
+    }
+
+    let mut flags = record.header.flags();
+    flags.remove(RecordFlag::Incomplete);
+    record.header.set_flags(flags);
+    record.content = content;
+    record.to_bytes()
+}
+
+/// Writes an unverified Solana RoA, replacing the current one
+pub fn write_roa(data: &[u8], roa_id: &[u8]) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = parse_complete(data)?;
+    record.header.right_of_association_validation = Validation::UnverifiedSolana as u16;
+    record.roa_id = checked_validation_id(Validation::UnverifiedSolana, roa_id)?;
+    record.to_bytes()
+}
+
+/// Removes the RoA, `verifier` must be the current RoA id
+pub fn unverify_roa(data: &[u8], verifier: &Pubkey) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = RecordView::parse(data)?;
+    if record.roa_id != verifier.as_ref() {
+        return Err(SnsRecordsError::InvalidVerifier);
+    }
+    record.header.right_of_association_validation = Validation::None as u16;
+    record.roa_id = &[];
+    record.to_bytes()
+}
+
+/// Stamps a new staleness id, which invalidates the current RoA and the RoA of the entries of a
+/// list record
+pub fn validate_staleness(
+    data: &[u8],
+    validation: Validation,
+    staleness_id: &[u8],
+) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = RecordView::parse(data)?;
+    record.header.staleness_validation = validation as u16;
+    record.header.right_of_association_validation = Validation::None as u16;
+    record.staleness_id = checked_validation_id(validation, staleness_id)?;
+    record.roa_id = &[];
+    if record.header.is_incomplete() || record.content_encoding()? != ContentEncoding::List {
+        return record.to_bytes();
+    }
+
+    let entries = record_list::parse_entries(record.content)?
+        .into_iter()
+        .map(|e| RecordEntry::new(e.content))
+        .collect::<Vec<_>>();
+    write_entries(record, &entries)
+}
+
+/// Marks the RoA as verified by the Solana `verifier`, which must be the current RoA id
+pub fn validate_solana_roa(data: &[u8], verifier: &Pubkey) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = parse_complete(data)?;
+    if record.roa_id != verifier.as_ref() {
+        return Err(SnsRecordsError::InvalidVerifier);
+    }
+    record.header.right_of_association_validation = Validation::Solana as u16;
+    record.to_bytes()
+}
+
+/// Verifies an ETH signature of the record message and writes `expected_pubkey` as the RoA
+///
+/// The staleness of the record must have been validated beforehand
+pub fn validate_ethereum_roa(
+    data: &[u8],
+    record_key: &Pubkey,
+    signature: &[u8],
+    expected_pubkey: &[u8],
+) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = parse_complete(data)?;
+
+    // Implicitly means that if the staleness is not verified it's
+    // impossible to verify the RoA
+    let staleness_id: [u8; 32] = record
+        .staleness_id
+        .try_into()
+        .map_err(|_| SnsRecordsError::OutOfBound)?;
+    let message = message_to_sign(
+        &record.committed_content()?,
+        record_key,
+        &Pubkey::from(staleness_id),
+    );
+
+    let eth_address = recover_eth_address(&message, signature)?;
+    if eth_address != expected_pubkey {
+        return Err(SnsRecordsError::EthPubkeyMismatch);
+    }
+
+    record.header.right_of_association_validation = Validation::Ethereum as u16;
+    record.roa_id = expected_pubkey;
+    record.to_bytes()
+}
+
+/// Checks a WebAuthn assertion of the record and writes the compressed P-256 `public_key` as the
+/// RoA
+///
+/// The assertion must be a `webauthn.get` for the [`WEBAUTHN_RP_ID`] relying party with the
+/// user present flag set, and its `clientDataJSON` challenge must be the base64url encoded
+/// [`webauthn_challenge`](super::message::webauthn_challenge) of the record. The signature
+/// itself is checked by the secp256r1 precompile, see
+/// [`webauthn_signed_message`](super::message::webauthn_signed_message). The staleness of the
+/// record must have been validated beforehand.
+pub fn validate_secp256r1_roa(
+    data: &[u8],
+    record_key: &Pubkey,
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    public_key: &[u8],
+) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = parse_complete(data)?;
+
+    let staleness_id: [u8; 32] = record
+        .staleness_id
+        .try_into()
+        .map_err(|_| SnsRecordsError::OutOfBound)?;
+
+    if public_key.len() != 33 || !matches!(public_key[0], 0x02 | 0x03) {
+        return Err(SnsRecordsError::InvalidWebAuthnAssertion);
+    }
+
+    // `rpIdHash (32) | flags (1) | signCount (4)`, the assertion must be scoped to our relying
+    // party and the user present flag must be set
+    if authenticator_data.len() < 37
+        || authenticator_data[..32] != hash(WEBAUTHN_RP_ID.as_bytes()).to_bytes()
+        || authenticator_data[32] & 0x01 == 0
+    {
+        return Err(SnsRecordsError::InvalidWebAuthnAssertion);
+    }
+
+    let challenge = webauthn_challenge(
+        &record.committed_content()?,
+        record_key,
+        &Pubkey::from(staleness_id),
+    );
+    let expected_challenge = URL_SAFE_NO_PAD.encode(challenge);
+    if client_data_field(client_data_json, "type") != Some(b"webauthn.get".as_ref())
+        || client_data_field(client_data_json, "challenge") != Some(expected_challenge.as_bytes())
+    {
+        return Err(SnsRecordsError::InvalidWebAuthnAssertion);
+    }
+
+    record.header.right_of_association_validation = Validation::Secp256r1 as u16;
+    record.roa_id = public_key;
+    record.to_bytes()
+}
+
+/// Verifies an ADR-36 (`signArbitrary`) signature of the record message by the Cosmos `address`
+/// and writes its 20 bytes account hash as the RoA, the address must be the content of the record
+///
+/// Both standard Cosmos `secp256k1` accounts (`ripemd160(sha256(compressed_pubkey))`, signing the
+/// SHA-256 of the sign doc) and Ethereum style `ethsecp256k1` accounts used by chains like
+/// Injective (`keccak256(pubkey)[12..]`, signing the Keccak-256 of the sign doc) are accepted.
+/// The staleness of the record must have been validated beforehand.
+pub fn validate_cosmos_roa(
+    data: &[u8],
+    record_key: &Pubkey,
+    address: &str,
+    signature: &[u8],
+) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = parse_complete(data)?;
+
+    let staleness_id: [u8; 32] = record
+        .staleness_id
+        .try_into()
+        .map_err(|_| SnsRecordsError::OutOfBound)?;
+
+    let decoded = bech32_decode(address).ok_or(SnsRecordsError::InvalidCosmosAddress)?;
+    let account_hash = &decoded.1;
+    if account_hash.len() != 20 {
+        return Err(SnsRecordsError::InvalidCosmosAddress);
+    }
+    // Addresses are short, only unencoded content is matched
+    if record.content_encoding()? != ContentEncoding::None {
+        return Err(SnsRecordsError::InvalidContentEncoding);
+    }
+    let content_address = std::str::from_utf8(record.content)
+        .ok()
+        .and_then(|content| bech32_decode(content.trim()));
+    if content_address.as_ref() != Some(&decoded) {
+        return Err(SnsRecordsError::AddressMismatch);
+    }
+
+    let message = record_message(
+        &record.committed_content()?,
+        record_key,
+        &Pubkey::from(staleness_id),
+    );
+    // Wallets sign with the canonical lowercase form of the address
+    let sign_doc = adr36_sign_doc(&address.to_ascii_lowercase(), &message);
+    let signature = signature.get(0..64).ok_or(SnsRecordsError::OutOfBound)?;
+
+    let signed_by = |digest: &[u8], derive: fn(&[u8; 64]) -> [u8; 20]| {
+        (0..2).any(|recovery_id| {
+            secp256k1_recover(digest, recovery_id, signature)
+                .map(|pubkey| derive(&pubkey.0) == account_hash[..])
+                .unwrap_or(false)
+        })
+    };
+    // `secp256k1` keys sign the SHA-256 of the sign doc, `ethsecp256k1` keys its Keccak-256
+    let signed = signed_by(hash(&sign_doc).as_ref(), cosmos_account_hash)
+        || signed_by(&keccak256(&sign_doc), eth_address);
+    if !signed {
+        return Err(SnsRecordsError::CosmosAddressMismatch);
+    }
+
+    record.header.right_of_association_validation = Validation::Cosmos as u16;
+    record.roa_id = account_hash;
+    record.to_bytes()
+}
+
+/// Writes the Sui address of the Ed25519 `public_key` as the RoA, the address must be the
+/// content of the record
+///
+/// The personal message signature itself is checked by the ed25519 precompile, see
+/// [`sui_signed_message`](super::message::sui_signed_message).
+pub fn validate_sui_roa(data: &[u8], public_key: &[u8]) -> Result<Vec<u8>, SnsRecordsError> {
+    let public_key: &[u8; 32] = public_key
+        .try_into()
+        .map_err(|_| SnsRecordsError::OutOfBound)?;
+    let mut preimage = [0; 33];
+    preimage[1..].copy_from_slice(public_key);
+    // Sui prefixes the public key with its signature scheme flag, 0x00 for Ed25519
+    let address = blake2b256(&preimage);
+    validate_move_roa(data, Validation::Sui, &address)
+}
+
+/// Writes the Aptos address of the Ed25519 `public_key` as the RoA, the address must be the
+/// content of the record
+///
+/// The message signature itself is checked by the ed25519 precompile, see
+/// [`aptos_signed_message`](super::message::aptos_signed_message).
+pub fn validate_aptos_roa(data: &[u8], public_key: &[u8]) -> Result<Vec<u8>, SnsRecordsError> {
+    let public_key: &[u8; 32] = public_key
+        .try_into()
+        .map_err(|_| SnsRecordsError::OutOfBound)?;
+    // Aptos suffixes the public key with its authentication scheme, 0x00 for single Ed25519
+    let address: [u8; 32] = Sha3_256::new()
+        .chain_update(public_key)
+        .chain_update([0])
+        .finalize()
+        .into();
+    validate_move_roa(data, Validation::Aptos, &address)
+}
+
+fn validate_move_roa(
+    data: &[u8],
+    validation: Validation,
+    address: &[u8; 32],
+) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = parse_complete(data)?;
+
+    // The signed message commits to the staleness id
+    if record.staleness_id.len() != 32 {
+        return Err(SnsRecordsError::OutOfBound);
+    }
+    // Addresses are short, only unencoded content is matched
+    if record.content_encoding()? != ContentEncoding::None {
+        return Err(SnsRecordsError::InvalidContentEncoding);
+    }
+    if content_address(record.content) != Some(*address) {
+        return Err(SnsRecordsError::AddressMismatch);
+    }
+
+    record.header.right_of_association_validation = validation as u16;
+    record.roa_id = address;
+    record.to_bytes()
+}
+
+/// Parses a 32 bytes Move address stored either raw or as a (possibly short) `0x` hex string
+fn content_address(content: &[u8]) -> Option<[u8; 32]> {
+    if let Ok(address) = content.try_into() {
+        return Some(address);
+    }
+    let s = std::str::from_utf8(content).ok()?.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if s.is_empty() || s.len() > 64 {
+        return None;
+    }
+    let mut address = [0; 32];
+    hex::decode_to_slice(format!("{:0>64}", s), &mut address).ok()?;
+    Some(address)
+}
+
+/// `ripemd160(sha256(compressed_pubkey))` of an uncompressed secp256k1 public key
+fn cosmos_account_hash(pubkey: &[u8; 64]) -> [u8; 20] {
+    let mut compressed = [0; 33];
+    compressed[0] = 0x02 | (pubkey[63] & 1);
+    compressed[1..].copy_from_slice(&pubkey[..32]);
+    ripemd160(hash(&compressed).as_ref())
+}
+
+/// The last 20 bytes of the Keccak-256 of an uncompressed secp256k1 public key
+fn eth_address(pubkey: &[u8; 64]) -> [u8; 20] {
+    let mut eth_address = [0; 20];
+    eth_address.copy_from_slice(&keccak256(pubkey)[12..]);
+    eth_address
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Hasher::default();
+    hasher.hash(data);
+    hasher.result().0
+}
+
+/// The value of a string field of a `clientDataJSON`
+///
+/// Authenticators serialize it without whitespace and neither the type nor the base64url
+/// challenge contain escaped characters, so a plain search is enough.
+fn client_data_field<'a>(client_data_json: &'a [u8], field: &str) -> Option<&'a [u8]> {
+    let pattern = format!("\"{}\":\"", field);
+    let start = client_data_json
+        .windows(pattern.len())
+        .position(|w| w == pattern.as_bytes())?
+        + pattern.len();
+    let rest = &client_data_json[start..];
+    let end = rest.iter().position(|c| *c == b'"')?;
+    Some(&rest[..end])
+}
+
+/// Writes the address of an Ethereum contract wallet as an attested RoA
+///
+/// The attestors check off-chain that the wallet approved the
+/// [`message_to_sign`](super::message::message_to_sign) of the record and co-sign the
+/// transaction. The staleness of the record must have been validated beforehand.
+pub fn validate_ethereum_attested_roa(
+    data: &[u8],
+    expected_pubkey: &[u8],
+) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut record = parse_complete(data)?;
+
+    if record.staleness_id.len() != 32 {
+        return Err(SnsRecordsError::OutOfBound);
+    }
+    if expected_pubkey.len() != 20 {
+        return Err(SnsRecordsError::EthPubkeyMismatch);
+    }
+
+    record.header.right_of_association_validation = Validation::EthereumAttested as u16;
+    record.roa_id = expected_pubkey;
+    record.to_bytes()
+}
+
+/// Appends an unverified entry to a list record
+///
+/// Like an edit, the staleness and the RoA of the record are reset, the RoA of the other entries
+/// are kept
+pub fn add_entry(data: &[u8], entry: &[u8]) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut entries = list_entries(&RecordView::parse(data)?)?;
+    entries.push(RecordEntry::new(entry.to_vec()));
+    fresh_record(
+        &record_list::serialize_entries(&entries)?,
+        ContentEncoding::List,
+    )
+}
+
+/// Removes the entry at `index` from a list record, resetting the staleness and the RoA of the
+/// record
+pub fn remove_entry(data: &[u8], index: u16) -> Result<Vec<u8>, SnsRecordsError> {
+    let mut entries = list_entries(&RecordView::parse(data)?)?;
+    if index as usize >= entries.len() {
+        return Err(SnsRecordsError::OutOfBound);
+    }
+    entries.remove(index as usize);
+    fresh_record(
+        &record_list::serialize_entries(&entries)?,
+        ContentEncoding::List,
+    )
+}
+
+/// Writes `verifier` as the RoA of the entry at `index`, the entry content must be the verifier
+/// public key
+pub fn validate_entry_solana_roa(
+    data: &[u8],
+    index: u16,
+    verifier: &Pubkey,
+) -> Result<Vec<u8>, SnsRecordsError> {
+    let record = RecordView::parse(data)?;
+    let mut entries = list_entries(&record)?;
+    let entry = entries
+        .get_mut(index as usize)
+        .ok_or(SnsRecordsError::OutOfBound)?;
+    if entry.content != verifier.as_ref() {
+        return Err(SnsRecordsError::InvalidVerifier);
+    }
+    entry.right_of_association_validation = Validation::Solana;
+    entry.roa_id = verifier.to_bytes().to_vec();
+
+    write_entries(record, &entries)
+}
+
+/// Verifies an ETH signature of the message of the entry at `index` and writes the recovered
+/// address, which must be the entry content, as the entry RoA
+///
+/// The message is the record message of the entry content, the staleness of the record must have
+/// been validated beforehand
+pub fn validate_entry_ethereum_roa(
+    data: &[u8],
+    index: u16,
+    record_key: &Pubkey,
+    signature: &[u8],
+) -> Result<Vec<u8>, SnsRecordsError> {
+    let record = RecordView::parse(data)?;
+    let staleness_id: [u8; 32] = record
+        .staleness_id
+        .try_into()
+        .map_err(|_| SnsRecordsError::OutOfBound)?;
+    let mut entries = list_entries(&record)?;
+    let entry = entries
+        .get_mut(index as usize)
+        .ok_or(SnsRecordsError::OutOfBound)?;
+
+    let message = message_to_sign(&entry.content, record_key, &Pubkey::from(staleness_id));
+    let eth_address = recover_eth_address(&message, signature)?;
+    if entry.content != eth_address {
+        return Err(SnsRecordsError::EthPubkeyMismatch);
+    }
+    entry.right_of_association_validation = Validation::Ethereum;
+    entry.roa_id = eth_address.to_vec();
+
+    write_entries(record, &entries)
+}
+
+/// Removes the RoA of the entry at `index`, `verifier` must be the current RoA id of the entry
+pub fn unverify_entry(
+    data: &[u8],
+    index: u16,
+    verifier: &Pubkey,
+) -> Result<Vec<u8>, SnsRecordsError> {
+    let record = RecordView::parse(data)?;
+    let mut entries = list_entries(&record)?;
+    let entry = entries
+        .get_mut(index as usize)
+        .ok_or(SnsRecordsError::OutOfBound)?;
+    if entry.roa_id != verifier.as_ref() {
+        return Err(SnsRecordsError::InvalidVerifier);
+    }
+    entry.right_of_association_validation = Validation::None;
+    entry.roa_id = vec![];
+
+    write_entries(record, &entries)
+}
+
+/// Serializes `entries` as the content of `record`, keeping its header flags and validations
+fn write_entries(record: RecordView, entries: &[RecordEntry]) -> Result<Vec<u8>, SnsRecordsError> {
+    let content = record_list::serialize_entries(entries)?;
+    let mut record = RecordView {
+        content: &content,
+        ..record
+    };
+    record
+        .header
+        .set_content_length(checked_content_length(content.len())?);
+    record.to_bytes()
+}
+
+fn list_entries(record: &RecordView) -> Result<Vec<RecordEntry>, SnsRecordsError> {
+    if record.header.is_incomplete() {
+        return Err(SnsRecordsError::UploadInProgress);
+    }
+    if record.content_encoding()? != ContentEncoding::List {
+        return Err(SnsRecordsError::InvalidContentEncoding);
+    }
+    record_list::parse_entries(record.content)
+}
+
+/// Parses a record whose content is complete, the RoA of a record being uploaded in chunks can't
+/// be validated since its content is not final
+fn parse_complete(data: &[u8]) -> Result<RecordView<'_>, SnsRecordsError> {
+    let record = RecordView::parse(data)?;
+    if record.header.is_incomplete() {
+        return Err(SnsRecordsError::UploadInProgress);
+    }
+    Ok(record)
+}
+
+fn checked_content_length(content_length: usize) -> Result<u32, SnsRecordsError> {
+    content_length
+        .try_into()
+        .ok()
+        .filter(|l| l & !RecordHeader::CONTENT_LENGTH_MASK == 0)
+        .ok_or(SnsRecordsError::NumericalOverflow)
+}
+
+/// Checks that `id` has the length of the ids of `validation`, which is how the id is read back
+fn checked_validation_id(validation: Validation, id: &[u8]) -> Result<&[u8], SnsRecordsError> {
+    if id.len() != get_validation_length(validation) as usize {
+        return Err(SnsRecordsError::OutOfBound);
+    }
+    Ok(id)
+}
+
+/// Recovers the ETH address that signed `message`
+fn recover_eth_address(message: &[u8], signature: &[u8]) -> Result<[u8; 20], SnsRecordsError> {
+    let recovery_id = signature
+        .get(64)
+        .ok_or(SnsRecordsError::OutOfBound)?
+        .checked_sub(27)
+        .ok_or(SnsRecordsError::NumericalOverflow)?;
+
+    let mut hasher = Hasher::default();
+    hasher.hash(message);
+    let hash = hasher.result();
+
+    let recovered_pubkey = secp256k1_recover(
+        hash.as_ref(),
+        recovery_id,
+        signature.get(0..64).ok_or(SnsRecordsError::OutOfBound)?,
+    )
+    .map_err(|_| SnsRecordsError::Secp256k1Recover)?;
+
+    Ok(eth_address(&recovered_pubkey.0))
+}
+
+/// The range of `new` that differs from `old`, `None` if both are identical
+///
+/// When the lengths differ, everything after the common prefix is considered changed
+pub fn changed_range(old: &[u8], new: &[u8]) -> Option<Range<usize>> {
+    let start = old
+        .iter()
+        .zip(new)
+        .position(|(a, b)| a != b)
+        .unwrap_or_else(|| old.len().min(new.len()));
+    if old.len() != new.len() {
+        return Some(start..new.len());
+    }
+    if start == new.len() {
+        return None;
+    }
+    let end = new.len()
+        - old
+            .iter()
+            .rev()
+            .zip(new.iter().rev())
+            .position(|(a, b)| a != b)
+            .unwrap_or(0);
+    Some(start..end)
+}
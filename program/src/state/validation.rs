@@ -5,7 +5,9 @@ use {
     borsh::{BorshDeserialize, BorshSerialize},
 };
 
-#[derive(BorshDeserialize, BorshSerialize, BorshSize, Clone, Copy, Debug)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
 #[repr(u16)]
 pub enum Validation {
     None,
@@ -13,6 +15,11 @@ pub enum Validation {
     Ethereum,
     UnverifiedSolana,
     XChain,
+    Secp256r1,
+    Cosmos,
+    Sui,
+    Aptos,
+    EthereumAttested,
 }
 
 impl TryFrom<u16> for Validation {
@@ -24,6 +31,11 @@ impl TryFrom<u16> for Validation {
             2 => Ok(Validation::Ethereum),
             3 => Ok(Validation::UnverifiedSolana),
             4 => Ok(Validation::XChain),
+            5 => Ok(Validation::Secp256r1),
+            6 => Ok(Validation::Cosmos),
+            7 => Ok(Validation::Sui),
+            8 => Ok(Validation::Aptos),
+            9 => Ok(Validation::EthereumAttested),
             _ => Err(crate::error::SnsRecordsError::DataTypeMismatch),
         }
     }
@@ -36,5 +48,10 @@ pub fn get_validation_length(validation: Validation) -> u32 {
         Validation::Solana => 32,
         Validation::UnverifiedSolana => 32,
         Validation::XChain => 34,
+        Validation::Secp256r1 => 33,
+        Validation::Cosmos => 20,
+        Validation::Sui => 32,
+        Validation::Aptos => 32,
+        Validation::EthereumAttested => 20,
     }
 }
@@ -1,11 +1,24 @@
-use solana_program::{account_info::AccountInfo, hash::hashv, program_pack::Pack};
+use solana_program::{
+    account_info::AccountInfo,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    hash::hashv,
+    program_error::ProgramError,
+    program_pack::Pack,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
 use spl_name_service::state::NameRecordHeader;
 use spl_name_service::state::HASH_PREFIX;
+use spl_token::{
+    instruction::MAX_SIGNERS,
+    state::{Account as TokenAccount, Multisig},
+};
 
 use crate::error::SnsRecordsError;
 
 use {
-    solana_program::pubkey, solana_program::pubkey::Pubkey,
+    bonfida_utils::checks::{check_account_key, check_account_owner, check_signer},
+    solana_program::pubkey,
+    solana_program::pubkey::Pubkey,
     spl_name_service::state::get_seeds_and_key,
 };
 
@@ -27,6 +40,18 @@ pub fn get_record_key_and_seeds(domain: &Pubkey, record: &str) -> (Pubkey, Vec<u
     )
 }
 
+/// The record key along with the hashed record name it is derived from
+pub fn get_record_key_and_hashed_name(domain: &Pubkey, record: &str) -> (Pubkey, Vec<u8>) {
+    let hashed = get_hashed_name(record);
+    let (key, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        hashed.clone(),
+        Some(&crate::central_state::KEY),
+        Some(domain),
+    );
+    (key, hashed)
+}
+
 pub fn check_domain_owner(
     account: &AccountInfo,
     expected_owner: &Pubkey,
@@ -41,6 +66,144 @@ pub fn check_domain_owner(
     Ok(())
 }
 
+pub const NAME_TOKENIZER_ID: Pubkey = pubkey!("nftD3vbNkNqfj2Sd3HZwbpw4BxxKWr4AjGb9X38JeZk");
+pub const NAME_TOKENIZER_MINT_PREFIX: &[u8] = b"tokenized_name";
+
+/// Returns the mint of the NFT of a domain tokenized by the name tokenizer
+pub fn get_domain_mint(domain: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[NAME_TOKENIZER_MINT_PREFIX, domain.as_ref()],
+        &NAME_TOKENIZER_ID,
+    )
+    .0
+}
+
+/// Returns the vault of the name tokenizer, the owner of every tokenized domain
+pub fn get_tokenizer_vault() -> Pubkey {
+    Pubkey::find_program_address(&[NAME_TOKENIZER_ID.as_ref()], &NAME_TOKENIZER_ID).0
+}
+
+/// Checks that `authority` is the domain owner, or the holder of the domain NFT when the domain is
+/// tokenized and `domain_token_account` is provided
+///
+/// A tokenized domain is owned by the vault of the name tokenizer, the holder proves ownership with
+/// a token account of the domain mint holding the NFT.
+pub fn check_domain_owner_or_holder(
+    domain: &AccountInfo,
+    authority: &Pubkey,
+    domain_token_account: Option<&AccountInfo>,
+) -> Result<(), ProgramError> {
+    let domain_token_account = match domain_token_account {
+        Some(domain_token_account) => domain_token_account,
+        None => return Ok(check_domain_owner(domain, authority)?),
+    };
+
+    check_domain_owner(domain, &get_tokenizer_vault())?;
+
+    check_account_owner(domain_token_account, &spl_token::ID)?;
+    let token_account = TokenAccount::unpack(&domain_token_account.data.borrow())?;
+    if token_account.mint != get_domain_mint(domain.key)
+        || token_account.owner != *authority
+        || token_account.amount != 1
+    {
+        return Err(SnsRecordsError::WrongTokenHolder.into());
+    }
+
+    Ok(())
+}
+
+/// Takes the next account if it satisfies `predicate`
+///
+/// Optional accounts are told apart from the optional accounts following them by their owner or
+/// signer status.
+pub fn next_account_info_if<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    predicate: impl Fn(&AccountInfo) -> bool,
+) -> Option<&'a AccountInfo<'b>> {
+    let account = accounts_iter
+        .as_slice()
+        .first()
+        .filter(|account| predicate(account))?;
+    accounts_iter.next();
+    Some(account)
+}
+
+/// Takes the config account at its position or, for the account layouts predating it, anywhere
+/// after the other accounts
+pub fn next_config_account<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    accounts: &'a [AccountInfo<'b>],
+    config_key: &Pubkey,
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    next_account_info_if(accounts_iter, |account| account.key == config_key)
+        .or_else(|| accounts.iter().find(|account| account.key == config_key))
+        .ok_or_else(|| SnsRecordsError::MissingConfig.into())
+}
+
+/// Takes the next account if it is a token account, the domain token account of the token-gated
+/// mode
+pub fn next_domain_token_account<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+) -> Option<&'a AccountInfo<'b>> {
+    next_account_info_if(accounts_iter, |account| account.owner == &spl_token::ID)
+}
+
+fn is_multisig(account: &AccountInfo) -> bool {
+    account.owner == &spl_token::ID && account.data_len() == Multisig::LEN
+}
+
+/// Checks that the domain owner signed, either directly or, when it is an SPL Token multisig,
+/// through at least `m` of its signers among `multisig_signers`
+pub fn check_domain_owner_signer(
+    domain_owner: &AccountInfo,
+    multisig_signers: &[AccountInfo],
+) -> Result<(), ProgramError> {
+    if !is_multisig(domain_owner) {
+        return check_signer(domain_owner);
+    }
+
+    let multisig = Multisig::unpack(&domain_owner.data.borrow())?;
+    let mut matched = [false; MAX_SIGNERS];
+    let mut num_signers = 0;
+    for signer in multisig_signers.iter().filter(|signer| signer.is_signer) {
+        for (position, key) in multisig.signers[..multisig.n as usize].iter().enumerate() {
+            if key == signer.key && !matched[position] {
+                matched[position] = true;
+                num_signers += 1;
+            }
+        }
+    }
+    if num_signers < multisig.m {
+        return Err(SnsRecordsError::NotEnoughMultisigSigners.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `domain_owner` owns the domain and signs directly, which the name service requires
+/// to create a record account under the domain
+///
+/// A tokenized domain is owned by the vault of the name tokenizer and a multisig owned domain by an
+/// SPL Token multisig, neither of which can sign the name service instruction: the holder has to
+/// redeem the domain, and a multisig has to transfer it to a signing owner, to allocate records.
+pub fn check_domain_owner_allocation(
+    domain: &AccountInfo,
+    domain_owner: &AccountInfo,
+) -> Result<(), ProgramError> {
+    let hd = NameRecordHeader::unpack_from_slice(&domain.data.borrow())
+        .map_err(|_| SnsRecordsError::DataTypeMismatch)?;
+    if hd.owner == get_tokenizer_vault() {
+        return Err(SnsRecordsError::TokenizedDomainAllocation.into());
+    }
+    if hd.owner != *domain_owner.key {
+        return Err(SnsRecordsError::WrongDomainOwner.into());
+    }
+    if is_multisig(domain_owner) {
+        return Err(SnsRecordsError::MultisigDomainAllocation.into());
+    }
+    check_signer(domain_owner)
+}
+
 pub fn check_domain_parent(
     account: &AccountInfo,
     expected_parent: &Pubkey,
@@ -58,3 +221,87 @@ pub fn check_domain_parent(
 
     Ok(())
 }
+
+/// Checks that `authority` is the upgrade authority of the program, `program_data` must be its
+/// `ProgramData` account
+pub fn check_upgrade_authority(
+    program_id: &Pubkey,
+    program_data: &AccountInfo,
+    authority: &Pubkey,
+) -> Result<(), ProgramError> {
+    let (program_data_key, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID);
+    check_account_key(program_data, &program_data_key)?;
+
+    // ProgramData { slot: u64, upgrade_authority_address: Option<Pubkey> } with a u32 tag
+    let data = program_data.data.borrow();
+    match data.get(..UpgradeableLoaderState::size_of_programdata_metadata()) {
+        Some([3, 0, 0, 0, _, _, _, _, _, _, _, _, 1, key @ ..]) if key == authority.as_ref() => {
+            Ok(())
+        }
+        _ => Err(SnsRecordsError::InvalidUpgradeAuthority.into()),
+    }
+}
+
+pub const SECP256R1_PROGRAM_ID: Pubkey = pubkey!("Secp256r1SigVerify1111111111111111111111111");
+
+/// Checks that the instruction preceding the current one is a signature verification by the
+/// `precompile` program (ed25519 or secp256r1) of `message` signed by `public_key`
+///
+/// The precompile instruction must verify a single signature whose data lives in the precompile
+/// instruction itself.
+pub fn check_precompile_verification(
+    instructions_sysvar: &AccountInfo,
+    precompile: &Pubkey,
+    public_key: &[u8],
+    message: &[u8],
+) -> Result<(), ProgramError> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let index = current_index
+        .checked_sub(1)
+        .ok_or(SnsRecordsError::InvalidPrecompileInstruction)?;
+    let instruction = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+    if instruction.program_id != *precompile {
+        return Err(SnsRecordsError::InvalidPrecompileInstruction.into());
+    }
+
+    let data = &instruction.data;
+    let read_u16 = |offset: usize| -> Result<u16, SnsRecordsError> {
+        data.get(offset..offset + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .ok_or(SnsRecordsError::OutOfBound)
+    };
+    if data.first() != Some(&1) {
+        return Err(SnsRecordsError::InvalidPrecompileInstruction.into());
+    }
+    // Offsets struct starts after the signature count and a padding byte
+    let signature_instruction_index = read_u16(4)?;
+    let public_key_offset = read_u16(6)? as usize;
+    let public_key_instruction_index = read_u16(8)?;
+    let message_offset = read_u16(10)? as usize;
+    let message_size = read_u16(12)? as usize;
+    let message_instruction_index = read_u16(14)?;
+
+    if [
+        signature_instruction_index,
+        public_key_instruction_index,
+        message_instruction_index,
+    ]
+    .iter()
+    .any(|i| *i != u16::MAX && *i != index)
+    {
+        return Err(SnsRecordsError::InvalidPrecompileInstruction.into());
+    }
+
+    let signed_public_key = data
+        .get(public_key_offset..public_key_offset + public_key.len())
+        .ok_or(SnsRecordsError::OutOfBound)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(SnsRecordsError::OutOfBound)?;
+    if signed_public_key != public_key || signed_message != message {
+        return Err(SnsRecordsError::InvalidPrecompileInstruction.into());
+    }
+
+    Ok(())
+}
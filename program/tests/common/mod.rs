@@ -0,0 +1,369 @@
+//! `solana-program-test` setup shared by the end-to-end tests
+#![allow(dead_code)]
+
+use borsh::BorshSerialize;
+use sns_records::{
+    central_state,
+    error::SnsRecordsError,
+    instruction::allocate_and_post_record,
+    state::{config::Config, content_encoding::ContentEncoding},
+    utils::get_record_key_and_seeds,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{Instruction, InstructionError},
+    keccak,
+    program_pack::Pack,
+    program_stubs::{self, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_name_service::{
+    instruction::NameRegistryInstruction,
+    state::{get_seeds_and_key, NameRecordHeader},
+};
+use spl_token::state::{Account as TokenAccount, AccountState, Multisig};
+use std::sync::{Mutex, Once};
+
+/// The records program and the name service, both as native processors
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "sns_records",
+        sns_records::ID,
+        processor!(sns_records::entrypoint::process_instruction),
+    );
+    program_test.add_program(
+        "spl_name_service",
+        spl_name_service::ID,
+        processor!(spl_name_service::processor::Processor::process_instruction),
+    );
+    program_test
+}
+
+/// The `sol_log_data` fields logged by the native processors of every test of the binary
+static LOG_DATA: Mutex<Vec<Vec<Vec<u8>>>> = Mutex::new(Vec::new());
+
+/// Native processors don't log `sol_log_data` to the transaction, these stubs keep the fields
+/// and forward every other syscall to the `solana-program-test` stubs
+struct LogDataStubs(Box<dyn SyscallStubs>);
+
+struct NoStubs;
+
+impl SyscallStubs for NoStubs {}
+
+impl SyscallStubs for LogDataStubs {
+    fn sol_log(&self, message: &str) {
+        self.0.sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.0.sol_log_compute_units()
+    }
+    fn sol_remaining_compute_units(&self) -> u64 {
+        self.0.sol_remaining_compute_units()
+    }
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.0
+            .sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_rent_sysvar(var_addr)
+    }
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_epoch_rewards_sysvar(var_addr)
+    }
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_last_restart_slot(var_addr)
+    }
+    unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.0.sol_memcpy(dst, src, n)
+    }
+    unsafe fn sol_memmove(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.0.sol_memmove(dst, src, n)
+    }
+    unsafe fn sol_memcmp(&self, s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+        self.0.sol_memcmp(s1, s2, n, result)
+    }
+    unsafe fn sol_memset(&self, s: *mut u8, c: u8, n: usize) {
+        self.0.sol_memset(s, c, n)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.0.sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.0.sol_set_return_data(data)
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields = fields.iter().map(|field| field.to_vec()).collect();
+        LOG_DATA.lock().unwrap().push(fields);
+    }
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.0.sol_get_processed_sibling_instruction(index)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.0.sol_get_stack_height()
+    }
+}
+
+/// Keeps the `sol_log_data` fields logged from now on, to be called once the test is started
+pub fn capture_log_data() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let stubs = program_stubs::set_syscall_stubs(Box::new(NoStubs));
+        program_stubs::set_syscall_stubs(Box::new(LogDataStubs(stubs)));
+    });
+}
+
+/// The `sol_log_data` fields captured so far, see `capture_log_data`
+pub fn log_data() -> Vec<Vec<Vec<u8>>> {
+    LOG_DATA.lock().unwrap().clone()
+}
+
+/// Adds an initialized config account
+pub fn add_config(program_test: &mut ProgramTest, config: &Config) {
+    let mut data = config.try_to_vec().unwrap();
+    data.resize(Config::LEN, 0);
+    program_test.add_account(
+        Config::find_key(&sns_records::ID).0,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: sns_records::ID,
+            ..Account::default()
+        },
+    );
+}
+
+pub async fn send(
+    ctx: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all_signers = vec![&ctx.payer];
+    all_signers.extend_from_slice(signers);
+    let blockhash = ctx.banks_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&ctx.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await
+}
+
+pub fn assert_error(result: Result<(), BanksClientError>, error: SnsRecordsError) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, error as u32)
+        }
+        e => panic!("expected {:?}, got {:?}", error, e),
+    }
+}
+
+/// Creates a top level name owned by `owner`
+pub async fn create_domain(ctx: &mut ProgramTestContext, name: &str, owner: &Pubkey) -> Pubkey {
+    let hashed_name = sns_records::utils::get_hashed_name(name);
+    let (domain, _) = get_seeds_and_key(&spl_name_service::ID, hashed_name.clone(), None, None);
+    let ix = spl_name_service::instruction::create(
+        spl_name_service::ID,
+        NameRegistryInstruction::Create {
+            hashed_name,
+            lamports: Rent::default().minimum_balance(NameRecordHeader::LEN),
+            space: 0,
+        },
+        domain,
+        ctx.payer.pubkey(),
+        *owner,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    send(ctx, &[ix], &[]).await.unwrap();
+    domain
+}
+
+/// Creates a subdomain of `parent` owned by `owner`
+pub async fn create_subdomain(
+    ctx: &mut ProgramTestContext,
+    parent: &Pubkey,
+    parent_owner: &Keypair,
+    name: &str,
+    owner: &Pubkey,
+) -> Pubkey {
+    let hashed_name = sns_records::utils::get_hashed_name(&format!("\0{}", name));
+    let (domain, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        hashed_name.clone(),
+        None,
+        Some(parent),
+    );
+    let ix = spl_name_service::instruction::create(
+        spl_name_service::ID,
+        NameRegistryInstruction::Create {
+            hashed_name,
+            lamports: Rent::default().minimum_balance(NameRecordHeader::LEN),
+            space: 0,
+        },
+        domain,
+        ctx.payer.pubkey(),
+        *owner,
+        None,
+        Some(*parent),
+        Some(parent_owner.pubkey()),
+    )
+    .unwrap();
+    send(ctx, &[ix], &[parent_owner]).await.unwrap();
+    domain
+}
+
+/// Transfers `domain` from `owner` to `new_owner` through the name service
+pub async fn transfer_domain(
+    ctx: &mut ProgramTestContext,
+    domain: &Pubkey,
+    owner: &Keypair,
+    new_owner: &Pubkey,
+) {
+    let ix = spl_name_service::instruction::transfer(
+        spl_name_service::ID,
+        *new_owner,
+        *domain,
+        owner.pubkey(),
+        None,
+    )
+    .unwrap();
+    send(ctx, &[ix], &[owner]).await.unwrap();
+}
+
+/// Allocates the `record` of `domain` with `content`, `owner` signing as the domain owner
+pub async fn post_record(
+    ctx: &mut ProgramTestContext,
+    domain: &Pubkey,
+    owner: &Keypair,
+    record: &str,
+    content: &[u8],
+) -> Pubkey {
+    let (key, _) = get_record_key_and_seeds(domain, record);
+    let ix = allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &ctx.payer.pubkey(),
+            record: &key,
+            domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &Config::find_key(&sns_records::ID).0,
+        },
+        allocate_and_post_record::Params {
+            record: record.to_owned(),
+            content: content.to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    send(ctx, &[ix], &[owner]).await.unwrap();
+    key
+}
+
+/// Adds an initialized `m` of `signers` SPL Token multisig
+pub fn add_multisig(ctx: &mut ProgramTestContext, m: u8, signers: &[Pubkey]) -> Pubkey {
+    let mut multisig = Multisig {
+        m,
+        n: signers.len() as u8,
+        is_initialized: true,
+        ..Multisig::default()
+    };
+    multisig.signers[..signers.len()].copy_from_slice(signers);
+    let mut data = vec![0; Multisig::LEN];
+    Multisig::pack(multisig, &mut data).unwrap();
+    add_token_program_account(ctx, data)
+}
+
+/// Adds a token account holding `amount` of `mint` for `owner`
+pub fn add_token_account(
+    ctx: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount::pack(
+        TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        },
+        &mut data,
+    )
+    .unwrap();
+    add_token_program_account(ctx, data)
+}
+
+fn add_token_program_account(ctx: &mut ProgramTestContext, data: Vec<u8>) -> Pubkey {
+    let key = Pubkey::new_unique();
+    ctx.set_account(
+        &key,
+        &Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: spl_token::ID,
+            ..Account::default()
+        }
+        .into(),
+    );
+    key
+}
+
+pub async fn account_data(ctx: &mut ProgramTestContext, key: &Pubkey) -> Option<Vec<u8>> {
+    ctx.banks_client
+        .get_account(*key)
+        .await
+        .unwrap()
+        .map(|a| a.data)
+}
+
+/// The record data following the `NameRecordHeader`
+pub async fn record_data(ctx: &mut ProgramTestContext, key: &Pubkey) -> Vec<u8> {
+    let data = account_data(ctx, key).await.expect("The record exists");
+    data[NameRecordHeader::LEN..].to_vec()
+}
+
+/// The Ethereum address of a secp256k1 key
+pub fn eth_address(secret_key: &libsecp256k1::SecretKey) -> [u8; 20] {
+    let public_key = libsecp256k1::PublicKey::from_secret_key(secret_key).serialize();
+    let mut address = [0; 20];
+    address.copy_from_slice(&keccak::hash(&public_key[1..]).0[12..]);
+    address
+}
+
+/// Signs the Keccak-256 of `message`, `r | s | v` with `v` in `27..=28`
+pub fn eth_sign(secret_key: &libsecp256k1::SecretKey, message: &[u8]) -> Vec<u8> {
+    let digest = libsecp256k1::Message::parse(&keccak::hash(message).0);
+    let (signature, recovery_id) = libsecp256k1::sign(&digest, secret_key);
+    let mut signature = signature.serialize().to_vec();
+    signature.push(recovery_id.serialize() + 27);
+    signature
+}
@@ -0,0 +1,243 @@
+#![cfg(feature = "test-bpf")]
+//! Compute unit benchmarks of the record hot paths, compared against the baseline program
+//!
+//! Both programs and the name service are loaded as SBF shared objects from `target/deploy`,
+//! where `build.sh` dumps the deployed records program as `sns_records_baseline.so` along with
+//! the name service before running:
+//!
+//! ```text
+//! cargo test-sbf --features test-bpf --test compute_units -- --nocapture
+//! ```
+
+mod common;
+
+use common::{create_domain, eth_address, eth_sign};
+use sns_records::{
+    central_state,
+    instruction::*,
+    state::{
+        config::Config,
+        content_encoding::{self, ContentEncoding},
+        message::message_to_sign,
+        validation::Validation,
+    },
+    utils::get_record_key_and_seeds,
+};
+use solana_program::{instruction::Instruction, system_program};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// Processes a single instruction and returns the compute units it consumed
+async fn measure(ctx: &mut ProgramTestContext, ix: Instruction, signer: &Keypair) -> u64 {
+    // A fresh blockhash so that repeating an instruction is not a duplicate transaction
+    let blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, signer],
+        blockhash,
+    );
+    let result = ctx
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    result.result.unwrap();
+    result.metadata.unwrap().compute_units_consumed
+}
+
+/// The baseline params did not have the trailing content encoding
+fn without_content_encoding(mut ix: Instruction) -> Instruction {
+    assert_eq!(ix.data.pop(), Some(ContentEncoding::None as u8));
+    ix
+}
+
+/// Runs the benchmarked instructions against the records program loaded from `program_name`
+async fn run(program_name: &str, baseline: bool) -> Vec<(&'static str, u64)> {
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(true);
+    program_test.add_program(program_name, sns_records::ID, None);
+    program_test.add_program("spl_name_service", spl_name_service::ID, None);
+    let mut ctx = program_test.start_with_context().await;
+
+    let owner = Keypair::new();
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "url");
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+    let encoding = |ix| {
+        if baseline {
+            without_content_encoding(ix)
+        } else {
+            ix
+        }
+    };
+
+    let ix = allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+        },
+        allocate_and_post_record::Params {
+            record: "url".to_owned(),
+            content: b"https://sns.id".to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    let allocate_and_post = measure(&mut ctx, encoding(ix), &owner).await;
+
+    let ix = edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: None,
+            parent_domain: None,
+            parent_authority: None,
+            multisig_signers: &[],
+        },
+        edit_record::Params {
+            record: "url".to_owned(),
+            content: b"https://bonfida.org".to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    let edit = measure(&mut ctx, encoding(ix), &owner).await;
+
+    let validate = validate_solana_signature(
+        validate_solana_signature::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            verifier: &owner.pubkey(),
+            config: &config,
+            domain_token_account: None,
+            multisig_signers: &[],
+        },
+        validate_solana_signature::Params { staleness: true },
+    );
+    // The first validation stamps the staleness id, the second one leaves the record as is
+    let validate_changed = measure(&mut ctx, validate.clone(), &owner).await;
+    let validate_unchanged = measure(&mut ctx, validate, &owner).await;
+
+    let mut results = vec![
+        ("AllocateAndPostRecord", allocate_and_post),
+        ("EditRecord", edit),
+        ("ValidateSolanaSignature", validate_changed),
+        ("ValidateSolanaSignature (unchanged)", validate_unchanged),
+    ];
+    if baseline {
+        return results;
+    }
+
+    // A long text record stored compressed, inflated once when written and once when the RoA
+    // message is built
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(64);
+    let (record, _) = get_record_key_and_seeds(&domain, "TXT");
+    let ix = allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+        },
+        allocate_and_post_record::Params {
+            record: "TXT".to_owned(),
+            content: content_encoding::encode(ContentEncoding::Deflate, text.as_bytes()),
+            content_encoding: ContentEncoding::Deflate,
+        },
+    );
+    results.push((
+        "AllocateAndPostRecord (deflate)",
+        measure(&mut ctx, ix, &owner).await,
+    ));
+
+    let ix = validate_solana_signature(
+        validate_solana_signature::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            verifier: &owner.pubkey(),
+            config: &config,
+            domain_token_account: None,
+            multisig_signers: &[],
+        },
+        validate_solana_signature::Params { staleness: true },
+    );
+    measure(&mut ctx, ix, &owner).await;
+
+    let secret_key = libsecp256k1::SecretKey::parse(&[0x11; 32]).unwrap();
+    let message = message_to_sign(text.as_bytes(), &record, &owner.pubkey());
+    let ix = validate_ethereum_signature(
+        validate_ethereum_signature::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: None,
+            multisig_signers: &[],
+        },
+        validate_ethereum_signature::Params {
+            validation: Validation::Ethereum,
+            signature: eth_sign(&secret_key, &message),
+            expected_pubkey: eth_address(&secret_key).to_vec(),
+        },
+    );
+    results.push((
+        "ValidateEthereumSignature (deflate)",
+        measure(&mut ctx, ix, &owner).await,
+    ));
+
+    results
+}
+
+#[tokio::test]
+async fn compute_units_against_baseline() {
+    let current = run("sns_records", false).await;
+    let baseline = run("sns_records_baseline", true).await;
+
+    println!("{:<36} {:>10} {:>10}", "Instruction", "Baseline", "Current");
+    for (i, (name, current)) in current.iter().enumerate() {
+        let baseline = baseline
+            .get(i)
+            .map_or_else(|| "-".to_owned(), |(_, units)| units.to_string());
+        println!("{:<36} {:>10} {:>10}", name, baseline, current);
+    }
+
+    // Only the changed bytes are written through the name service
+    assert!(current[0].1 < baseline[0].1);
+    assert!(current[1].1 < baseline[1].1);
+    // An unchanged record is not written back through the name service
+    assert!(current[3].1 < current[2].1);
+    assert!(current[3].1 < baseline[3].1);
+}
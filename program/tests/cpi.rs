@@ -0,0 +1,188 @@
+use std::sync::{Arc, Mutex};
+
+use borsh::BorshDeserialize;
+use sns_records::{
+    cpi::records::{invoke_allocate_and_post_record, invoke_edit_record},
+    instruction::{allocate_and_post_record, edit_record, ProgramInstruction},
+    state::{config::Config, content_encoding::ContentEncoding},
+    utils::get_record_key_and_seeds,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    system_program,
+};
+
+/// An invocation of the records program captured by the syscall stubs
+struct Invocation {
+    instruction: Instruction,
+    signers: Vec<Pubkey>,
+    account_infos: Vec<Pubkey>,
+}
+
+#[derive(Clone, Default)]
+struct RecordInvocations(Arc<Mutex<Vec<Invocation>>>);
+
+impl SyscallStubs for RecordInvocations {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &REGISTRAR_ID).unwrap())
+            .collect();
+        self.0.lock().unwrap().push(Invocation {
+            instruction: instruction.clone(),
+            signers,
+            account_infos: account_infos.iter().map(|a| *a.key).collect(),
+        });
+        Ok(())
+    }
+}
+
+const REGISTRAR_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TestAccount {
+    fn new(key: Pubkey, owner: Pubkey) -> Self {
+        Self {
+            key,
+            owner,
+            lamports: 0,
+            data: vec![],
+        }
+    }
+
+    fn info(&mut self, is_signer: bool, is_writable: bool) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            is_signer,
+            is_writable,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+#[test]
+fn pda_owned_domain_posts_records() {
+    let invocations = RecordInvocations::default();
+    set_syscall_stubs(Box::new(invocations.clone()));
+
+    // The registrar owns the domain through a PDA derived from it
+    let domain_key = Pubkey::new_unique();
+    let (domain_owner_key, nonce) =
+        Pubkey::find_program_address(&[domain_key.as_ref()], &REGISTRAR_ID);
+    let signer_seeds: &[&[u8]] = &[domain_key.as_ref(), &[nonce]];
+    let (record_key, _) = get_record_key_and_seeds(&domain_key, "SOL");
+
+    let mut system = TestAccount::new(system_program::ID, Pubkey::default());
+    let mut name_service = TestAccount::new(spl_name_service::ID, Pubkey::default());
+    let mut fee_payer = TestAccount::new(Pubkey::new_unique(), system_program::ID);
+    let mut record = TestAccount::new(record_key, system_program::ID);
+    let mut domain = TestAccount::new(domain_key, spl_name_service::ID);
+    let mut domain_owner = TestAccount::new(domain_owner_key, REGISTRAR_ID);
+    let mut central_state = TestAccount::new(sns_records::central_state::KEY, system_program::ID);
+    let mut config = TestAccount::new(Config::find_key(&sns_records::ID).0, sns_records::ID);
+
+    let system = system.info(false, false);
+    let name_service = name_service.info(false, false);
+    let fee_payer = fee_payer.info(true, true);
+    let record = record.info(false, true);
+    let domain = domain.info(false, true);
+    let domain_owner = domain_owner.info(false, true);
+    let central_state = central_state.info(false, false);
+    let config = config.info(false, false);
+
+    invoke_allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: &system,
+            spl_name_service_program: &name_service,
+            fee_payer: &fee_payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &domain_owner,
+            central_state: &central_state,
+            config: &config,
+        },
+        allocate_and_post_record::Params {
+            record: "SOL".to_owned(),
+            content: domain_owner_key.to_bytes().to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+        &[signer_seeds],
+    )
+    .unwrap();
+
+    invoke_edit_record(
+        edit_record::Accounts {
+            system_program: &system,
+            spl_name_service_program: &name_service,
+            fee_payer: &fee_payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &domain_owner,
+            central_state: &central_state,
+            config: &config,
+            domain_token_account: None,
+            parent_domain: None,
+            parent_authority: None,
+            multisig_signers: &[],
+        },
+        edit_record::Params {
+            record: "SOL".to_owned(),
+            content: fee_payer.key.to_bytes().to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+        &[signer_seeds],
+    )
+    .unwrap();
+
+    let invocations = invocations.0.lock().unwrap();
+    assert_eq!(invocations.len(), 2);
+    for (invocation, tag) in invocations.iter().zip([
+        ProgramInstruction::AllocateAndPostRecord,
+        ProgramInstruction::EditRecord,
+    ]) {
+        let Invocation {
+            instruction,
+            signers,
+            account_infos,
+        } = invocation;
+        assert_eq!(instruction.program_id, sns_records::ID);
+        assert_eq!(instruction.data[0], tag as u8);
+        assert_eq!(signers, &[domain_owner_key]);
+
+        let metas = instruction
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect::<Vec<_>>();
+        assert_eq!(&metas, account_infos);
+        assert_eq!(metas.len(), 8);
+        let owner_meta = &instruction.accounts[5];
+        assert_eq!(owner_meta.pubkey, domain_owner_key);
+        assert!(owner_meta.is_signer && owner_meta.is_writable);
+    }
+
+    let params =
+        allocate_and_post_record::Params::try_from_slice(&invocations[0].instruction.data[1..])
+            .unwrap();
+    assert_eq!(params.record, "SOL");
+    assert_eq!(params.content, domain_owner_key.to_bytes());
+}
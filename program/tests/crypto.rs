@@ -0,0 +1,154 @@
+use sns_records::crypto::{bech32_decode, bech32_encode, blake2b, blake2b256, ripemd160};
+
+/// The test vectors of the RIPEMD-160 specification (Dobbertin, Bosselaers, Preneel)
+#[test]
+fn ripemd160_spec_vectors() {
+    let vectors: &[(&[u8], &str)] = &[
+        (b"", "9c1185a5c5e9fc54612808977ee8f548b2258d31"),
+        (b"a", "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe"),
+        (b"abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+        (
+            b"message digest",
+            "5d0689ef49d2fae572b881b123a85ffa21595f36",
+        ),
+        (
+            b"abcdefghijklmnopqrstuvwxyz",
+            "f71c27109c692c1b56bbdceb5b9d2865b3708dbc",
+        ),
+        (
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+            "12a053384a9c0c88e405a06c27dcf49ada62eb2b",
+        ),
+        (
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+            "b0e20b6e3116640286ed3a87a5713079b21f5189",
+        ),
+    ];
+    for (input, digest) in vectors {
+        assert_eq!(hex::encode(ripemd160(input)), *digest);
+    }
+
+    assert_eq!(
+        hex::encode(ripemd160("1234567890".repeat(8).as_bytes())),
+        "9b752e45573d4b39f4dbd3323cab82bf63326bfb"
+    );
+    assert_eq!(
+        hex::encode(ripemd160(&[b'a'; 1_000_000])),
+        "52783243c1697bdbe16d37f97f68f08325dc1528"
+    );
+}
+
+/// The valid bech32 strings of BIP-173
+#[test]
+fn bech32_valid_vectors() {
+    let vectors = [
+        "A12UEL5L",
+        "a12uel5l",
+        "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+        "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+        "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j",
+        "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+        "?1ezyfcl",
+    ];
+    for s in vectors.iter() {
+        let (hrp, data) = bech32_decode(s).unwrap_or_else(|| panic!("{} is valid", s));
+        assert_eq!(hrp, s[..s.rfind('1').unwrap()].to_ascii_lowercase());
+        // Encoding is canonical, lowercase
+        assert_eq!(bech32_encode(&hrp, &data), s.to_ascii_lowercase());
+    }
+
+    let (hrp, data) = bech32_decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw").unwrap();
+    assert_eq!(hrp, "abcdef");
+    assert_eq!(
+        hex::encode(data),
+        "00443214c74254b635cf84653a56d7c675be77df"
+    );
+}
+
+/// The invalid bech32 strings of BIP-173
+#[test]
+fn bech32_invalid_vectors() {
+    let vectors = [
+        // HRP character out of range
+        "\u{20}1nwldj5",
+        "\u{7f}1axkwrx",
+        "\u{80}1eym55h",
+        // Overall max length exceeded
+        "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+        // No separator character
+        "pzry9x0s0muk",
+        // Empty HRP
+        "1pzry9x0s0muk",
+        "10a06t8",
+        "1qzzfhee",
+        // Invalid data character
+        "x1b4n0q5v",
+        // Too short checksum
+        "li1dgmt3",
+        // Invalid character in checksum
+        "de1lg7wt\u{ff}",
+        // Checksum calculated with uppercase form of HRP
+        "A1G7SGD8",
+        // Mixed case
+        "a12UEL5L",
+    ];
+    for s in vectors.iter() {
+        assert!(bech32_decode(s).is_none(), "{:?} is invalid", s);
+    }
+}
+
+#[test]
+fn bech32_cosmos_address() {
+    let address = "cosmos12vfxp232rx0z9rzn0hay9jptagk8c86dn5t56m";
+    let (hrp, account_hash) = bech32_decode(address).unwrap();
+    assert_eq!(hrp, "cosmos");
+    assert_eq!(account_hash.len(), 20);
+    assert_eq!(bech32_encode("cosmos", &account_hash), address);
+    // A single substitution is always detected
+    assert!(bech32_decode("cosmos12vfxp232rx0z9rzn0hay9jptagk8c86dn5t56n").is_none());
+}
+
+/// The BLAKE2b-512 vector of RFC 7693 Appendix A
+#[test]
+fn blake2b_rfc7693_vector() {
+    assert_eq!(
+        hex::encode(blake2b::<64>(b"abc")),
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+         7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+    );
+    assert_eq!(
+        hex::encode(blake2b::<64>(b"")),
+        "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419\
+         d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+    );
+}
+
+/// BLAKE2b-256 with an empty, a single full block and a multiple blocks input
+#[test]
+fn blake2b256_vectors() {
+    let full_block = (0..128).collect::<Vec<u8>>();
+    let mut blocks = (0..=255).cycle().take(1024).collect::<Vec<u8>>();
+    blocks.push(b'x');
+
+    let vectors: &[(&[u8], &str)] = &[
+        (
+            b"",
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+        ),
+        (
+            b"abc",
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319",
+        ),
+        (
+            &full_block,
+            "c3582f71ebb2be66fa5dd750f80baae97554f3b015663c8be377cfcb2488c1d1",
+        ),
+        (
+            &blocks,
+            "c374b2f6691d48b7aab5f898227b4bbe2bee9f885d1140fbe15ecb3ab5d01b8f",
+        ),
+    ];
+    for (input, digest) in vectors {
+        assert_eq!(hex::encode(blake2b256(input)), *digest);
+    }
+}
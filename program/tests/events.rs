@@ -0,0 +1,64 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::BorshSerialize;
+use sns_records::{
+    events::{RecordEvent, EVENT_TAG, PROGRAM_DATA_PREFIX},
+    instruction::ProgramInstruction,
+    state::validation::Validation,
+};
+use solana_program::pubkey::Pubkey;
+
+fn event(record_name_hash: Option<[u8; 32]>, content_hash: Option<[u8; 32]>) -> RecordEvent {
+    RecordEvent {
+        operation: ProgramInstruction::ValidateSolanaSignature,
+        record: Pubkey::new_from_array([1; 32]),
+        domain: Pubkey::new_from_array([2; 32]),
+        record_name_hash,
+        staleness_validation: Validation::Solana,
+        right_of_association_validation: Validation::Ethereum,
+        content_hash,
+    }
+}
+
+/// The log line `sol_log_data` prints for `fields`
+fn log_line(fields: &[&[u8]]) -> String {
+    let fields = fields
+        .iter()
+        .map(|f| STANDARD.encode(f))
+        .collect::<Vec<_>>();
+    format!("{}{}", PROGRAM_DATA_PREFIX, fields.join(" "))
+}
+
+#[test]
+fn events_round_trip_through_their_log_line() {
+    for event in [event(Some([3; 32]), Some([4; 32])), event(None, None)] {
+        let data = event.try_to_vec().unwrap();
+        assert!(data.len() <= RecordEvent::MAX_LEN);
+        assert_eq!(
+            RecordEvent::decode(&[EVENT_TAG, &data]),
+            Some(event.clone())
+        );
+        assert_eq!(
+            RecordEvent::decode_log(&log_line(&[EVENT_TAG, &data])),
+            Some(event)
+        );
+    }
+    let data = event(Some([3; 32]), Some([4; 32])).try_to_vec().unwrap();
+    assert_eq!(data.len(), RecordEvent::MAX_LEN);
+}
+
+#[test]
+fn other_logs_are_not_events() {
+    let data = event(None, None).try_to_vec().unwrap();
+    assert_eq!(RecordEvent::decode(&[b"other", &data]), None);
+    assert_eq!(RecordEvent::decode(&[EVENT_TAG]), None);
+    assert_eq!(RecordEvent::decode(&[EVENT_TAG, &data[..data.len() - 1]]), None);
+    assert_eq!(RecordEvent::decode_log(&log_line(&[b"other", &data])), None);
+    assert_eq!(
+        RecordEvent::decode_log("Program log: Instruction: EditRecord"),
+        None
+    );
+    assert_eq!(
+        RecordEvent::decode_log(&format!("{}not base64!", PROGRAM_DATA_PREFIX)),
+        None
+    );
+}
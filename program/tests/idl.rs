@@ -0,0 +1,14 @@
+#![cfg(feature = "idl")]
+
+use std::{fs, path::Path};
+
+#[test]
+fn checked_in_idl_is_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("idl/sns_records.json");
+    let checked_in: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    assert_eq!(
+        checked_in,
+        sns_records::idl::generate(),
+        "Regenerate the IDL with `cargo run --features cli -- idl > idl/sns_records.json`"
+    );
+}
@@ -0,0 +1,381 @@
+#![cfg(feature = "indexer")]
+
+use borsh::BorshSerialize;
+use miniz_oxide::deflate::compress_to_vec;
+use sns_records::{
+    indexer::{RecordStore, Staleness},
+    record_kind::RecordKind,
+    state::{
+        content_encoding::ContentEncoding,
+        record_list::{self, RecordEntry},
+        transition,
+        validation::Validation,
+    },
+    utils::{get_domain_mint, get_record_key_and_seeds, get_tokenizer_vault},
+};
+use sns_warp_common::state::x_domain::XDomain;
+use solana_program::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use spl_name_service::state::NameRecordHeader;
+use spl_token::state::{Account as TokenAccount, AccountState};
+
+fn name_account(parent: Pubkey, owner: Pubkey, class: Pubkey, data: &[u8]) -> Vec<u8> {
+    let mut account = vec![0; NameRecordHeader::LEN];
+    NameRecordHeader {
+        parent_name: parent,
+        owner,
+        class,
+    }
+    .pack_into_slice(&mut account);
+    account.extend_from_slice(data);
+    account
+}
+
+fn domain_account(owner: Pubkey) -> Vec<u8> {
+    name_account(Pubkey::new_unique(), owner, Pubkey::default(), &[])
+}
+
+fn record_account(domain: Pubkey, record: &[u8]) -> Vec<u8> {
+    name_account(
+        domain,
+        sns_records::central_state::KEY,
+        sns_records::central_state::KEY,
+        record,
+    )
+}
+
+/// A record whose staleness was validated by `staleness_id` and whose RoA was validated by
+/// `verifier`
+fn validated_record(content: &[u8], validation: Validation, staleness_id: &[u8]) -> Vec<u8> {
+    let verifier = Pubkey::new_unique();
+    let data = transition::new_record(content, ContentEncoding::None).unwrap();
+    let data = transition::validate_staleness(&data, validation, staleness_id).unwrap();
+    let data = transition::write_roa(&data, verifier.as_ref()).unwrap();
+    transition::validate_solana_roa(&data, &verifier).unwrap()
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+#[test]
+fn groups_records_by_domain() {
+    let (owner, domain, other_domain) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (sol, _) = get_record_key_and_seeds(&domain, "SOL");
+    let (url, _) = get_record_key_and_seeds(&domain, "url");
+    let (other, _) = get_record_key_and_seeds(&other_domain, "SOL");
+    let custom = Pubkey::new_unique();
+
+    let accounts = [
+        (
+            sol,
+            record_account(
+                domain,
+                &validated_record(owner.as_ref(), Validation::Solana, owner.as_ref()),
+            ),
+        ),
+        (
+            url,
+            record_account(
+                domain,
+                &transition::new_record(
+                    &compress_to_vec(b"https://sns.id", 6),
+                    ContentEncoding::Deflate,
+                )
+                .unwrap(),
+            ),
+        ),
+        (
+            other,
+            record_account(
+                other_domain,
+                &transition::new_record(b"other", ContentEncoding::None).unwrap(),
+            ),
+        ),
+        (
+            custom,
+            record_account(
+                domain,
+                &transition::new_record(b"custom", ContentEncoding::None).unwrap(),
+            ),
+        ),
+        (domain, domain_account(owner)),
+    ];
+    let store = RecordStore::from_accounts(accounts.iter().map(|(k, d)| (*k, &d[..])));
+    assert_eq!(store.skipped_accounts().count(), 0);
+    assert_eq!(store.domain_owner(&domain), Some(&owner));
+
+    let mut records = store.domain_records(&domain);
+    records.sort_by_key(|r| r.record.content.clone());
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].record.content, owner.as_ref());
+    assert_eq!(records[1].record.content, b"custom");
+    assert_eq!(records[1].kind, None);
+    assert_eq!(records[2].record.content, b"https://sns.id");
+
+    let sol = store.domain_record(&domain, "SOL").unwrap();
+    assert_eq!(sol.kind, Some(RecordKind::Sol));
+    assert_eq!(sol.staleness, Staleness::Fresh);
+    assert!(sol.roa_verified);
+    let url = store.domain_record(&domain, "url").unwrap();
+    assert_eq!(url.record.content_encoding, ContentEncoding::Deflate);
+    assert_eq!(url.staleness, Staleness::Unverified);
+    assert!(!url.roa_verified);
+
+    assert_eq!(store.domain_records(&other_domain).len(), 1);
+    assert!(store.domain_record(&other_domain, "url").is_none());
+    assert_eq!(store.verified_domain_records(&domain).len(), 1);
+}
+
+#[test]
+fn records_go_stale_when_the_domain_is_transferred() {
+    let (owner, domain) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (sol, _) = get_record_key_and_seeds(&domain, "SOL");
+    let record = record_account(
+        domain,
+        &validated_record(owner.as_ref(), Validation::Solana, owner.as_ref()),
+    );
+
+    let mut store = RecordStore::from_accounts([(sol, &record[..])]);
+    assert_eq!(store.record(&sol).unwrap().staleness, Staleness::Unknown);
+
+    store
+        .insert_account(domain, &domain_account(owner))
+        .unwrap();
+    assert_eq!(store.record(&sol).unwrap().staleness, Staleness::Fresh);
+
+    store
+        .insert_account(domain, &domain_account(Pubkey::new_unique()))
+        .unwrap();
+    let status = store.record(&sol).unwrap();
+    assert_eq!(status.staleness, Staleness::Stale);
+    assert!(!status.roa_verified);
+    assert!(store.verified_domain_records(&domain).is_empty());
+}
+
+#[test]
+fn list_records_surface_their_entry_validations() {
+    let (owner, domain) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (wallets, _) = get_record_key_and_seeds(&domain, "wallets");
+    let (verifier, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let entries = [
+        RecordEntry::new(verifier.to_bytes().to_vec()),
+        RecordEntry::new(other.to_bytes().to_vec()),
+    ];
+    let data = transition::new_record(
+        &record_list::serialize_entries(&entries).unwrap(),
+        ContentEncoding::List,
+    )
+    .unwrap();
+    let data = transition::validate_staleness(&data, Validation::Solana, owner.as_ref()).unwrap();
+    let data = transition::validate_entry_solana_roa(&data, 0, &verifier).unwrap();
+    let record = record_account(domain, &data);
+    let domain_data = domain_account(owner);
+
+    let mut store =
+        RecordStore::from_accounts([(wallets, &record[..]), (domain, &domain_data[..])]);
+    let status = store.record(&wallets).unwrap();
+    assert_eq!(status.staleness, Staleness::Fresh);
+    assert!(!status.roa_verified);
+    assert_eq!(status.entries.len(), 2);
+    assert_eq!(
+        status.entries[0].right_of_association_validation,
+        Validation::Solana
+    );
+    assert_eq!(
+        status.verified_entries().collect::<Vec<_>>(),
+        [&status.entries[0]]
+    );
+    assert_eq!(store.verified_domain_records(&domain).len(), 1);
+
+    // The entry RoAs are only trusted while the record is fresh
+    store
+        .insert_account(domain, &domain_account(Pubkey::new_unique()))
+        .unwrap();
+    let status = store.record(&wallets).unwrap();
+    assert_eq!(status.staleness, Staleness::Stale);
+    assert_eq!(status.verified_entries().count(), 0);
+    assert!(store.verified_domain_records(&domain).is_empty());
+}
+
+#[test]
+fn tokenized_domains_are_evaluated_against_the_nft_holder() {
+    let (holder, domain) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (sol, _) = get_record_key_and_seeds(&domain, "SOL");
+    let (token_account_key, mint) = (Pubkey::new_unique(), get_domain_mint(&domain));
+
+    let mut store = RecordStore::new();
+    store
+        .insert_account(domain, &domain_account(get_tokenizer_vault()))
+        .unwrap();
+    store
+        .insert_account(
+            sol,
+            &record_account(
+                domain,
+                &validated_record(holder.as_ref(), Validation::Solana, holder.as_ref()),
+            ),
+        )
+        .unwrap();
+    // The holder is unknown until the token account is provided
+    assert_eq!(store.record(&sol).unwrap().staleness, Staleness::Unknown);
+
+    store
+        .insert_token_account(token_account_key, &token_account(mint, holder, 1))
+        .unwrap();
+    assert_eq!(store.record(&sol).unwrap().staleness, Staleness::Fresh);
+    assert!(store.record(&sol).unwrap().roa_verified);
+
+    // The NFT moves to another holder
+    store
+        .insert_token_account(token_account_key, &token_account(mint, holder, 0))
+        .unwrap();
+    assert_eq!(store.record(&sol).unwrap().staleness, Staleness::Unknown);
+    store
+        .insert_token_account(
+            Pubkey::new_unique(),
+            &token_account(mint, Pubkey::new_unique(), 1),
+        )
+        .unwrap();
+    assert_eq!(store.record(&sol).unwrap().staleness, Staleness::Stale);
+}
+
+#[test]
+fn multisig_owned_domains_are_evaluated_against_the_multisig() {
+    // The staleness of a multisig owned domain is stamped with the multisig key
+    let (multisig, domain) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (sol, _) = get_record_key_and_seeds(&domain, "SOL");
+    let accounts = [
+        (domain, domain_account(multisig)),
+        (
+            sol,
+            record_account(
+                domain,
+                &validated_record(multisig.as_ref(), Validation::Solana, multisig.as_ref()),
+            ),
+        ),
+    ];
+    let store = RecordStore::from_accounts(accounts.iter().map(|(k, d)| (*k, &d[..])));
+    assert_eq!(store.record(&sol).unwrap().staleness, Staleness::Fresh);
+}
+
+#[test]
+fn x_chain_domains_are_evaluated_against_the_x_domain() {
+    let (x_domain_key, domain) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (eth, _) = get_record_key_and_seeds(&domain, "ETH");
+    let x_domain = XDomain {
+        nonce: 255,
+        owner_chain: 2,
+        owner_address: [7; 32],
+        name_account: domain,
+    };
+    let mut staleness_id = 2u16.to_le_bytes().to_vec();
+    staleness_id.extend_from_slice(&[7; 32]);
+
+    let mut store = RecordStore::new();
+    store
+        .insert_account(domain, &domain_account(x_domain_key))
+        .unwrap();
+    store
+        .insert_account(
+            eth,
+            &record_account(
+                domain,
+                &validated_record(&[7; 20], Validation::XChain, &staleness_id),
+            ),
+        )
+        .unwrap();
+    assert_eq!(store.record(&eth).unwrap().staleness, Staleness::Unknown);
+
+    store
+        .insert_x_domain(x_domain_key, &x_domain.try_to_vec().unwrap())
+        .unwrap();
+    assert_eq!(store.record(&eth).unwrap().staleness, Staleness::Fresh);
+
+    let transferred = XDomain {
+        owner_address: [8; 32],
+        ..x_domain
+    };
+    store
+        .insert_x_domain(x_domain_key, &transferred.try_to_vec().unwrap())
+        .unwrap();
+    assert_eq!(store.record(&eth).unwrap().staleness, Staleness::Stale);
+}
+
+#[test]
+fn undecodable_accounts_are_skipped() {
+    let (owner, domain) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (sol, _) = get_record_key_and_seeds(&domain, "SOL");
+    let (url, _) = get_record_key_and_seeds(&domain, "url");
+    let truncated = Pubkey::new_unique();
+
+    // An unknown validation in the header and an account shorter than a name record header
+    let mut corrupted = transition::new_record(b"https://sns.id", ContentEncoding::None).unwrap();
+    corrupted[0] = 0xff;
+    let accounts = [
+        (domain, domain_account(owner)),
+        (url, record_account(domain, &corrupted)),
+        (truncated, vec![0; 10]),
+        (
+            sol,
+            record_account(
+                domain,
+                &transition::new_record(owner.as_ref(), ContentEncoding::None).unwrap(),
+            ),
+        ),
+    ];
+    let mut store = RecordStore::from_accounts(accounts.iter().map(|(k, d)| (*k, &d[..])));
+
+    let mut skipped = store
+        .skipped_accounts()
+        .map(|(k, _)| *k)
+        .collect::<Vec<_>>();
+    skipped.sort();
+    let mut expected = vec![url, truncated];
+    expected.sort();
+    assert_eq!(skipped, expected);
+    assert_eq!(store.domain_records(&domain).len(), 1);
+    assert!(store.domain_record(&domain, "SOL").is_some());
+
+    // A valid snapshot of a skipped account replaces the error
+    store
+        .insert_account(
+            url,
+            &record_account(
+                domain,
+                &transition::new_record(b"https://sns.id", ContentEncoding::None).unwrap(),
+            ),
+        )
+        .unwrap();
+    assert_eq!(store.skipped_accounts().count(), 1);
+    assert_eq!(store.domain_records(&domain).len(), 2);
+}
+
+#[test]
+fn incomplete_records_are_not_indexed() {
+    let (owner, domain) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (pic, _) = get_record_key_and_seeds(&domain, "pic");
+    let data = transition::new_chunked_record(4, &[0; 32], ContentEncoding::Deflate).unwrap();
+    let accounts = [
+        (domain, domain_account(owner)),
+        (pic, record_account(domain, &data)),
+    ];
+    let store = RecordStore::from_accounts(accounts.iter().map(|(k, d)| (*k, &d[..])));
+    assert_eq!(store.skipped_accounts().count(), 0);
+    assert!(store.record(&pic).is_none());
+}
@@ -0,0 +1,115 @@
+use std::convert::TryFrom;
+
+use sns_records::{
+    error::SnsRecordsError,
+    state::{
+        content_encoding::ContentEncoding,
+        off_chain::{OffChainPointer, PointerKind},
+        record::{DecodedRecord, RecordView},
+        transition,
+    },
+};
+
+const PAYLOAD: &[u8] = b"{\"name\":\"bonfida\"}";
+const CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+const ARWEAVE_ID: &str = "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U";
+const URL: &str = "https://bonfida.org/records/bonfida.json";
+
+#[test]
+fn pointers_round_trip_through_their_stored_content() {
+    let cases = [
+        (format!("ipfs://{}", CID), PointerKind::Ipfs, CID),
+        (
+            format!("ar://{}", ARWEAVE_ID),
+            PointerKind::Arweave,
+            ARWEAVE_ID,
+        ),
+        (URL.to_owned(), PointerKind::Https, URL),
+    ];
+    for (uri, kind, location) in cases {
+        let pointer = OffChainPointer::from_uri(&uri, PAYLOAD).unwrap();
+        assert_eq!(pointer.kind, kind);
+        assert_eq!(pointer.location, location);
+        assert_eq!(pointer.uri(), uri);
+
+        let content = pointer.to_bytes();
+        assert_eq!(content[0], kind as u8);
+        assert_eq!(OffChainPointer::parse(&content).unwrap(), pointer);
+    }
+}
+
+#[test]
+fn malformed_pointers_are_rejected() {
+    let pointer = OffChainPointer::from_uri(URL, PAYLOAD).unwrap();
+    let content = pointer.to_bytes();
+
+    let mut unknown_kind = content.clone();
+    unknown_kind[0] = 3;
+    let mut bad_utf8 = content.clone();
+    bad_utf8.push(0xff);
+    let malformed = [
+        &content[..OffChainPointer::HASH_OFFSET + 31],
+        &unknown_kind[..],
+        &bad_utf8[..],
+        // A pointer without location
+        &content[..OffChainPointer::HASH_OFFSET + 32],
+    ];
+    for content in malformed {
+        let result = OffChainPointer::parse(content);
+        assert!(matches!(
+            result,
+            Err(SnsRecordsError::InvalidOffChainPointer)
+        ));
+    }
+
+    let bad_locations = [
+        (PointerKind::Ipfs, "bafy/beig"),
+        (PointerKind::Arweave, &ARWEAVE_ID[1..]),
+        (
+            PointerKind::Arweave,
+            "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt.U",
+        ),
+        (PointerKind::Https, "http://bonfida.org"),
+        (PointerKind::Https, "https://"),
+    ];
+    for (kind, location) in bad_locations {
+        let result = OffChainPointer::new(kind, PAYLOAD, location.to_owned());
+        assert!(matches!(
+            result,
+            Err(SnsRecordsError::InvalidOffChainPointer)
+        ));
+
+        let mut content = vec![kind as u8];
+        content.extend_from_slice(&pointer.hash);
+        content.extend_from_slice(location.as_bytes());
+        let result = OffChainPointer::parse(&content);
+        assert!(matches!(
+            result,
+            Err(SnsRecordsError::InvalidOffChainPointer)
+        ));
+    }
+}
+
+#[test]
+fn verify_payload_checks_the_committed_hash() {
+    let pointer = OffChainPointer::from_uri(URL, PAYLOAD).unwrap();
+    pointer.verify_payload(PAYLOAD).unwrap();
+    let result = pointer.verify_payload(b"{\"name\":\"other\"}");
+    assert!(matches!(result, Err(SnsRecordsError::ContentHashMismatch)));
+
+    let data = transition::new_record(&pointer.to_bytes(), ContentEncoding::OffChain).unwrap();
+    let record = DecodedRecord::try_from(RecordView::parse(&data).unwrap()).unwrap();
+    assert_eq!(record.off_chain_pointer().unwrap(), Some(pointer));
+    record.verify_payload(PAYLOAD).unwrap();
+    let result = record.verify_payload(&PAYLOAD[1..]);
+    assert!(matches!(result, Err(SnsRecordsError::ContentHashMismatch)));
+
+    // Only off-chain records commit to a payload
+    let data = transition::new_record(PAYLOAD, ContentEncoding::None).unwrap();
+    let record = DecodedRecord::try_from(RecordView::parse(&data).unwrap()).unwrap();
+    let result = record.verify_payload(PAYLOAD);
+    assert!(matches!(
+        result,
+        Err(SnsRecordsError::InvalidContentEncoding)
+    ));
+}
@@ -0,0 +1,1353 @@
+mod common;
+
+use std::convert::TryInto;
+
+use borsh::BorshDeserialize;
+use common::*;
+use sns_records::{
+    central_state,
+    cpi::records::{invoke_allocate_and_post_record, invoke_delete_record, invoke_edit_record},
+    error::SnsRecordsError,
+    events::RecordEvent,
+    instruction::*,
+    record_kind::{max_content_length, MAX_CONTENT_LENGTH},
+    state::{
+        attestor_set::AttestorSet, config::Config, content_encoding::ContentEncoding,
+        parent_authority::ParentAuthority, record::RecordView, validation::Validation,
+    },
+    utils::{get_domain_mint, get_hashed_name, get_record_key_and_seeds, get_tokenizer_vault},
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+};
+use solana_program_test::processor;
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+};
+use spl_name_service::state::{get_seeds_and_key, NameRecordHeader};
+use spl_token::state::Multisig;
+
+/// Moves the config account after the other accounts, where the clients predating it add it
+fn append_config(mut ix: Instruction) -> Instruction {
+    let config = Config::find_key(&sns_records::ID).0;
+    let position = ix.accounts.iter().position(|a| a.pubkey == config).unwrap();
+    let meta = ix.accounts.remove(position);
+    ix.accounts.push(meta);
+    ix
+}
+
+/// Instructions built with the account layout predating the config account go through once the
+/// config account is added after the other accounts
+#[tokio::test]
+async fn baseline_layouts_take_the_config_after_the_other_accounts() {
+    let mut ctx = program_test().start_with_context().await;
+    let owner = Keypair::new();
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "SOL");
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+
+    let ix = allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+        },
+        allocate_and_post_record::Params {
+            record: "SOL".to_owned(),
+            content: owner.pubkey().to_bytes().to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    send(&mut ctx, &[append_config(ix)], &[&owner])
+        .await
+        .unwrap();
+
+    let new_content = Pubkey::new_unique();
+    let edit = edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: None,
+            parent_domain: None,
+            parent_authority: None,
+            multisig_signers: &[],
+        },
+        edit_record::Params {
+            record: "SOL".to_owned(),
+            content: new_content.to_bytes().to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    send(&mut ctx, &[append_config(edit)], &[&owner])
+        .await
+        .unwrap();
+
+    let ix = validate_solana_signature(
+        validate_solana_signature::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            verifier: &owner.pubkey(),
+            config: &config,
+            domain_token_account: None,
+            multisig_signers: &[],
+        },
+        validate_solana_signature::Params { staleness: true },
+    );
+    send(&mut ctx, &[append_config(ix)], &[&owner])
+        .await
+        .unwrap();
+
+    let data = record_data(&mut ctx, &record).await;
+    let view = RecordView::parse(&data).unwrap();
+    assert_eq!(view.staleness_id, owner.pubkey().as_ref());
+    assert_eq!(view.content, new_content.as_ref());
+
+    let ix = delete_record(
+        delete_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: None,
+            multisig_signers: &[],
+        },
+        delete_record::Params {},
+    );
+    send(&mut ctx, &[append_config(ix)], &[&owner])
+        .await
+        .unwrap();
+    assert!(account_data(&mut ctx, &record).await.is_none());
+}
+
+/// Drops the `content_encoding` byte ending the params, as the clients predating it do
+fn drop_content_encoding(mut ix: Instruction) -> Instruction {
+    ix.data.pop();
+    ix
+}
+
+/// The params of the clients predating content encodings still deserialize, with no encoding
+#[tokio::test]
+async fn params_without_content_encoding_default_to_none() {
+    let mut ctx = program_test().start_with_context().await;
+    let owner = Keypair::new();
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "TXT");
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+
+    let ix = allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+        },
+        allocate_and_post_record::Params {
+            record: "TXT".to_owned(),
+            content: b"bonfida".to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    send(&mut ctx, &[drop_content_encoding(ix)], &[&owner])
+        .await
+        .unwrap();
+    let data = record_data(&mut ctx, &record).await;
+    let view = RecordView::parse(&data).unwrap();
+    assert_eq!(
+        view.header.content_encoding().unwrap(),
+        ContentEncoding::None
+    );
+    assert_eq!(view.content, b"bonfida");
+
+    let ix = edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: None,
+            parent_domain: None,
+            parent_authority: None,
+            multisig_signers: &[],
+        },
+        edit_record::Params {
+            record: "TXT".to_owned(),
+            content: b"bonfida.sol".to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    send(&mut ctx, &[drop_content_encoding(ix)], &[&owner])
+        .await
+        .unwrap();
+    let data = record_data(&mut ctx, &record).await;
+    let view = RecordView::parse(&data).unwrap();
+    assert_eq!(
+        view.header.content_encoding().unwrap(),
+        ContentEncoding::None
+    );
+    assert_eq!(view.content, b"bonfida.sol");
+}
+
+#[test]
+fn hardcoded_config_key_matches_its_derivation() {
+    assert_eq!(
+        Config::find_key(&sns_records::ID),
+        (Config::KEY, Config::NONCE)
+    );
+}
+
+/// Well-known records are bounded by the limit of their kind even without a config account
+#[tokio::test]
+async fn well_known_records_enforce_the_limit_of_their_kind() {
+    let mut ctx = program_test().start_with_context().await;
+    let owner = Keypair::new();
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "SOL");
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+    let max_content_length = max_content_length("SOL") as usize;
+    assert!(max_content_length < MAX_CONTENT_LENGTH as usize);
+
+    let post = |content_length: usize| {
+        allocate_and_post_record(
+            allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &payer,
+                record: &record,
+                domain: &domain,
+                domain_owner: &owner.pubkey(),
+                central_state: &central_state::KEY,
+                config: &config,
+            },
+            allocate_and_post_record::Params {
+                record: "SOL".to_owned(),
+                content: vec![1; content_length],
+                content_encoding: ContentEncoding::None,
+            },
+        )
+    };
+    let result = send(&mut ctx, &[post(max_content_length + 1)], &[&owner]).await;
+    assert_error(result, SnsRecordsError::ContentTooLarge);
+    send(&mut ctx, &[post(max_content_length)], &[&owner])
+        .await
+        .unwrap();
+
+    let edit = edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: None,
+            parent_domain: None,
+            parent_authority: None,
+            multisig_signers: &[],
+        },
+        edit_record::Params {
+            record: "SOL".to_owned(),
+            content: vec![2; max_content_length + 1],
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    let result = send(&mut ctx, &[edit], &[&owner]).await;
+    assert_error(result, SnsRecordsError::ContentTooLarge);
+
+    // Other records are bounded by the global limit
+    let result = Config::default().check_content_length("custom", MAX_CONTENT_LENGTH as usize + 1);
+    assert!(matches!(result, Err(SnsRecordsError::ContentTooLarge)));
+    Config::default()
+        .check_content_length("custom", MAX_CONTENT_LENGTH as usize)
+        .unwrap();
+}
+
+/// The limits of the config apply to the baseline instructions wherever the config account is
+#[tokio::test]
+async fn baseline_instructions_enforce_the_config_limits() {
+    let mut program_test = program_test();
+    add_config(
+        &mut program_test,
+        &Config {
+            enabled_validations: !(1 << Validation::Solana as u16),
+            max_content_length: 16,
+            ..Config::default()
+        },
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let owner = Keypair::new();
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "url");
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+
+    let post = |content: &[u8]| {
+        allocate_and_post_record(
+            allocate_and_post_record::Accounts {
+                system_program: &system_program::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &payer,
+                record: &record,
+                domain: &domain,
+                domain_owner: &owner.pubkey(),
+                central_state: &central_state::KEY,
+                config: &config,
+            },
+            allocate_and_post_record::Params {
+                record: "url".to_owned(),
+                content: content.to_vec(),
+                content_encoding: ContentEncoding::None,
+            },
+        )
+    };
+    let too_long = post(b"https://bonfida.org");
+    let result = send(&mut ctx, std::slice::from_ref(&too_long), &[&owner]).await;
+    assert_error(result, SnsRecordsError::ContentTooLarge);
+    let result = send(&mut ctx, &[append_config(too_long)], &[&owner]).await;
+    assert_error(result, SnsRecordsError::ContentTooLarge);
+    send(
+        &mut ctx,
+        &[append_config(post(b"https://sns.id"))],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+
+    let ix = validate_solana_signature(
+        validate_solana_signature::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            verifier: &owner.pubkey(),
+            config: &config,
+            domain_token_account: None,
+            multisig_signers: &[],
+        },
+        validate_solana_signature::Params { staleness: false },
+    );
+    let result = send(&mut ctx, &[append_config(ix)], &[&owner]).await;
+    assert_error(result, SnsRecordsError::ValidationDisabled);
+}
+
+#[tokio::test]
+async fn paused_instructions_fail_closed() {
+    let admin = Keypair::new();
+    let mut program_test = program_test();
+    add_config(
+        &mut program_test,
+        &Config {
+            admin: admin.pubkey(),
+            paused_instructions: 1 << ProgramInstruction::SetParentAuthority as u8,
+            ..Config::default()
+        },
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let owner = Keypair::new();
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+    let (parent_authority, _) = ParentAuthority::find_key(&domain, &sns_records::ID);
+
+    let mut ix = set_parent_authority(
+        set_parent_authority::Accounts {
+            system_program: &system_program::ID,
+            fee_payer: &payer,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            parent_authority: &parent_authority,
+            config: &config,
+            domain_token_account: None,
+            multisig_signers: &[],
+        },
+        set_parent_authority::Params { enabled: true },
+    );
+    let result = send(&mut ctx, &[ix.clone()], &[&owner]).await;
+    assert_error(result, SnsRecordsError::InstructionPaused);
+
+    // Leaving the config account out does not get around the pause
+    let config_meta = ix.accounts.pop().unwrap();
+    let result = send(&mut ctx, &[ix.clone()], &[&owner]).await;
+    assert_error(result, SnsRecordsError::MissingConfig);
+    ix.accounts.push(config_meta);
+
+    let unpause = update_config(
+        update_config::Accounts {
+            config: &config,
+            admin: &admin.pubkey(),
+        },
+        update_config::Params {
+            admin: None,
+            paused: None,
+            paused_instructions: Some(0),
+            enabled_validations: None,
+            max_content_length: None,
+        },
+    );
+    send(&mut ctx, &[unpause, ix], &[&owner, &admin])
+        .await
+        .unwrap();
+    let data = account_data(&mut ctx, &parent_authority).await.unwrap();
+    assert_eq!(&data[..32], owner.pubkey().as_ref());
+}
+
+/// The pause applies to the instructions predating the config account, which can't get around it
+/// by leaving the config account out
+#[tokio::test]
+async fn paused_baseline_instructions_fail_closed() {
+    let mut program_test = program_test();
+    add_config(
+        &mut program_test,
+        &Config {
+            paused_instructions: 1 << ProgramInstruction::ValidateEthereumSignature as u8,
+            ..Config::default()
+        },
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let owner = Keypair::new();
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "ETH");
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+
+    let ix = validate_ethereum_signature(
+        validate_ethereum_signature::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: None,
+            multisig_signers: &[],
+        },
+        validate_ethereum_signature::Params {
+            validation: Validation::Ethereum,
+            signature: vec![0; 65],
+            expected_pubkey: vec![0; 20],
+        },
+    );
+    let result = send(&mut ctx, std::slice::from_ref(&ix), &[&owner]).await;
+    assert_error(result, SnsRecordsError::InstructionPaused);
+    let result = send(&mut ctx, &[append_config(ix.clone())], &[&owner]).await;
+    assert_error(result, SnsRecordsError::InstructionPaused);
+
+    let mut without_config = ix;
+    without_config.accounts.retain(|a| a.pubkey != config);
+    let result = send(&mut ctx, &[without_config], &[&owner]).await;
+    assert_error(result, SnsRecordsError::MissingConfig);
+}
+
+const REGISTRAR_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// A registrar owning domains through a PDA derived from the domain, which manages their records
+/// through the CPI helpers
+///
+/// The instruction data is a tag, 0 to post, 1 to edit and 2 to delete the `SOL` record, followed
+/// by the record content
+fn registrar(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let system_program = next_account_info(accounts_iter)?;
+    let spl_name_service_program = next_account_info(accounts_iter)?;
+    let fee_payer = next_account_info(accounts_iter)?;
+    let record = next_account_info(accounts_iter)?;
+    let domain = next_account_info(accounts_iter)?;
+    let domain_owner = next_account_info(accounts_iter)?;
+    let central_state = next_account_info(accounts_iter)?;
+    let config = next_account_info(accounts_iter)?;
+
+    let (owner_key, nonce) = Pubkey::find_program_address(&[domain.key.as_ref()], program_id);
+    assert_eq!(domain_owner.key, &owner_key);
+    let signer_seeds: &[&[u8]] = &[domain.key.as_ref(), &[nonce]];
+
+    let (tag, content) = data.split_first().unwrap();
+    match tag {
+        0 => invoke_allocate_and_post_record(
+            allocate_and_post_record::Accounts {
+                system_program,
+                spl_name_service_program,
+                fee_payer,
+                record,
+                domain,
+                domain_owner,
+                central_state,
+                config,
+            },
+            allocate_and_post_record::Params {
+                record: "SOL".to_owned(),
+                content: content.to_vec(),
+                content_encoding: ContentEncoding::None,
+            },
+            &[signer_seeds],
+        ),
+        1 => invoke_edit_record(
+            edit_record::Accounts {
+                system_program,
+                spl_name_service_program,
+                fee_payer,
+                record,
+                domain,
+                domain_owner,
+                central_state,
+                config,
+                domain_token_account: None,
+                parent_domain: None,
+                parent_authority: None,
+                multisig_signers: &[],
+            },
+            edit_record::Params {
+                record: "SOL".to_owned(),
+                content: content.to_vec(),
+                content_encoding: ContentEncoding::None,
+            },
+            &[signer_seeds],
+        ),
+        _ => invoke_delete_record(
+            delete_record::Accounts {
+                system_program,
+                spl_name_service_program,
+                fee_payer,
+                record,
+                domain,
+                domain_owner,
+                central_state,
+                config,
+                domain_token_account: None,
+                multisig_signers: &[],
+            },
+            delete_record::Params {},
+            &[signer_seeds],
+        ),
+    }
+}
+
+#[tokio::test]
+async fn pda_owned_domain_manages_records_through_cpi() {
+    let mut program_test = program_test();
+    program_test.add_program("registrar", REGISTRAR_ID, processor!(registrar));
+    let mut ctx = program_test.start_with_context().await;
+
+    let hashed_name = get_hashed_name("registrar");
+    let (domain, _) = get_seeds_and_key(&spl_name_service::ID, hashed_name, None, None);
+    let (domain_owner, _) = Pubkey::find_program_address(&[domain.as_ref()], &REGISTRAR_ID);
+    create_domain(&mut ctx, "registrar", &domain_owner).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "SOL");
+
+    let call = |tag: u8, content: &[u8]| {
+        let mut data = vec![tag];
+        data.extend_from_slice(content);
+        Instruction::new_with_bytes(
+            REGISTRAR_ID,
+            &data,
+            vec![
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(spl_name_service::ID, false),
+                AccountMeta::new(ctx.payer.pubkey(), true),
+                AccountMeta::new(record, false),
+                AccountMeta::new(domain, false),
+                AccountMeta::new(domain_owner, false),
+                AccountMeta::new_readonly(central_state::KEY, false),
+                AccountMeta::new_readonly(Config::find_key(&sns_records::ID).0, false),
+                AccountMeta::new_readonly(sns_records::ID, false),
+            ],
+        )
+    };
+
+    let (post, edit, delete) = (
+        call(0, b"https://sns.id"),
+        call(1, b"https://bonfida.org"),
+        call(2, &[]),
+    );
+    send(&mut ctx, &[post], &[]).await.unwrap();
+    let data = record_data(&mut ctx, &record).await;
+    assert_eq!(RecordView::parse(&data).unwrap().content, b"https://sns.id");
+
+    send(&mut ctx, &[edit], &[]).await.unwrap();
+    let data = record_data(&mut ctx, &record).await;
+    assert_eq!(
+        RecordView::parse(&data).unwrap().content,
+        b"https://bonfida.org"
+    );
+
+    send(&mut ctx, &[delete], &[]).await.unwrap();
+    assert!(account_data(&mut ctx, &record).await.is_none());
+}
+
+#[tokio::test]
+async fn refresh_staleness_restamps_other_validations_of_the_owner_key() {
+    let mut ctx = program_test().start_with_context().await;
+    let owner = Keypair::new();
+    let owner_key = owner.pubkey();
+    let domain = create_domain(&mut ctx, "bonfida", &owner_key).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "SUI");
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+
+    let ix = allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner_key,
+            central_state: &central_state::KEY,
+            config: &config,
+        },
+        allocate_and_post_record::Params {
+            record: "SUI".to_owned(),
+            content: vec![7; 32],
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+
+    // A 32 bytes staleness id of another validation which happens to be the owner key
+    let mut account = ctx.banks_client.get_account(record).await.unwrap().unwrap();
+    let data = {
+        let mut view = RecordView::parse(&account.data[NameRecordHeader::LEN..]).unwrap();
+        view.header.staleness_validation = Validation::Sui as u16;
+        view.staleness_id = owner_key.as_ref();
+        view.to_bytes().unwrap()
+    };
+    account.data.truncate(NameRecordHeader::LEN);
+    account.data.extend_from_slice(&data);
+    account.lamports = Rent::default().minimum_balance(account.data.len());
+    ctx.set_account(&record, &account.into());
+
+    let ix = refresh_staleness(
+        refresh_staleness::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            domain: &domain,
+            domain_owner: &owner_key,
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: None,
+            records: &[record],
+            multisig_signers: &[],
+        },
+        refresh_staleness::Params {},
+    );
+    send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+
+    let data = record_data(&mut ctx, &record).await;
+    let view = RecordView::parse(&data).unwrap();
+    assert_eq!(view.staleness_validation().unwrap(), Validation::Solana);
+    assert_eq!(view.staleness_id, owner_key.as_ref());
+}
+
+#[tokio::test]
+async fn tokenized_domains_cannot_allocate_records() {
+    let mut ctx = program_test().start_with_context().await;
+    let holder = Keypair::new();
+    let domain = create_domain(&mut ctx, "bonfida", &get_tokenizer_vault()).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "SOL");
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+
+    let ix = allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &holder.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+        },
+        allocate_and_post_record::Params {
+            record: "SOL".to_owned(),
+            content: holder.pubkey().to_bytes().to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    let result = send(&mut ctx, &[ix], &[&holder]).await;
+    assert_error(result, SnsRecordsError::TokenizedDomainAllocation);
+}
+
+#[tokio::test]
+async fn multisig_owned_domains_cannot_allocate_records() {
+    let mut ctx = program_test().start_with_context().await;
+    let multisig = Keypair::new();
+    let signer = Keypair::new();
+    let mut data = vec![0; Multisig::LEN];
+    let mut signers = [Pubkey::default(); spl_token::instruction::MAX_SIGNERS];
+    signers[0] = signer.pubkey();
+    Multisig::pack(
+        Multisig {
+            m: 1,
+            n: 1,
+            is_initialized: true,
+            signers,
+        },
+        &mut data,
+    )
+    .unwrap();
+    ctx.set_account(
+        &multisig.pubkey(),
+        &Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: spl_token::ID,
+            ..Account::default()
+        }
+        .into(),
+    );
+    let domain = create_domain(&mut ctx, "bonfida", &multisig.pubkey()).await;
+    let (record, _) = get_record_key_and_seeds(&domain, "SOL");
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+
+    // Even when the multisig address itself could sign
+    let ix = allocate_and_post_record(
+        allocate_and_post_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &multisig.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+        },
+        allocate_and_post_record::Params {
+            record: "SOL".to_owned(),
+            content: signer.pubkey().to_bytes().to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    let result = send(&mut ctx, &[ix], &[&multisig]).await;
+    assert_error(result, SnsRecordsError::MultisigDomainAllocation);
+}
+
+/// Builds an edit of the `TXT` record of `domain` by the owner of its `parent` domain
+fn parent_edit(
+    payer: &Pubkey,
+    domain: &Pubkey,
+    parent: &Pubkey,
+    parent_owner: &Pubkey,
+    content: &[u8],
+) -> Instruction {
+    edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: payer,
+            record: &get_record_key_and_seeds(domain, "TXT").0,
+            domain,
+            domain_owner: parent_owner,
+            central_state: &central_state::KEY,
+            config: &Config::find_key(&sns_records::ID).0,
+            domain_token_account: None,
+            parent_domain: Some(parent),
+            parent_authority: Some(&ParentAuthority::find_key(domain, &sns_records::ID).0),
+            multisig_signers: &[],
+        },
+        edit_record::Params {
+            record: "TXT".to_owned(),
+            content: content.to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    )
+}
+
+/// Builds the opt-in, or opt-out, of `domain` to the parent authority mode
+fn set_parent_authority_ix(
+    payer: &Pubkey,
+    domain: &Pubkey,
+    domain_owner: &Pubkey,
+    domain_token_account: Option<&Pubkey>,
+    multisig_signers: &[Pubkey],
+    enabled: bool,
+) -> Instruction {
+    set_parent_authority(
+        set_parent_authority::Accounts {
+            system_program: &system_program::ID,
+            fee_payer: payer,
+            domain,
+            domain_owner,
+            parent_authority: &ParentAuthority::find_key(domain, &sns_records::ID).0,
+            config: &Config::find_key(&sns_records::ID).0,
+            domain_token_account,
+            multisig_signers,
+        },
+        set_parent_authority::Params { enabled },
+    )
+}
+
+#[tokio::test]
+async fn parent_authority_lets_the_parent_owner_edit_records() {
+    let mut ctx = program_test().start_with_context().await;
+    let parent_owner = Keypair::new();
+    let owner = Keypair::new();
+    let parent = create_domain(&mut ctx, "bonfida", &parent_owner.pubkey()).await;
+    let domain = create_subdomain(&mut ctx, &parent, &parent_owner, "sub", &owner.pubkey()).await;
+    let record = post_record(&mut ctx, &domain, &owner, "TXT", b"before").await;
+    let payer = ctx.payer.pubkey();
+
+    // The parent authority account exists but the subdomain owner opted out
+    let ix = set_parent_authority_ix(&payer, &domain, &owner.pubkey(), None, &[], false);
+    send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+    let ix = parent_edit(&payer, &domain, &parent, &parent_owner.pubkey(), b"after");
+    let result = send(&mut ctx, &[ix], &[&parent_owner]).await;
+    assert_error(result, SnsRecordsError::ParentAuthorityDisabled);
+
+    let ix = set_parent_authority_ix(&payer, &domain, &owner.pubkey(), None, &[], true);
+    send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+    let ix = parent_edit(&payer, &domain, &parent, &parent_owner.pubkey(), b"after");
+    send(&mut ctx, &[ix], &[&parent_owner]).await.unwrap();
+    let data = record_data(&mut ctx, &record).await;
+    assert_eq!(RecordView::parse(&data).unwrap().content, b"after");
+
+    // The opt-in lapses when the subdomain is transferred
+    transfer_domain(&mut ctx, &domain, &owner, &Pubkey::new_unique()).await;
+    let ix = parent_edit(&payer, &domain, &parent, &parent_owner.pubkey(), b"later");
+    let result = send(&mut ctx, &[ix], &[&parent_owner]).await;
+    assert_error(result, SnsRecordsError::ParentAuthorityDisabled);
+}
+
+/// A multisig owner opts in through its signers, with the builder leaving the multisig unsigned,
+/// and the holder of a tokenized subdomain through its token account
+#[tokio::test]
+async fn multisig_and_tokenized_subdomains_opt_in_to_the_parent_authority() {
+    let mut ctx = program_test().start_with_context().await;
+    let parent_owner = Keypair::new();
+    let owner = Keypair::new();
+    let signer = Keypair::new();
+    let holder = Keypair::new();
+    let payer = ctx.payer.pubkey();
+    let parent = create_domain(&mut ctx, "bonfida", &parent_owner.pubkey()).await;
+
+    let multisig = add_multisig(&mut ctx, 1, &[signer.pubkey()]);
+    let domain = create_subdomain(
+        &mut ctx,
+        &parent,
+        &parent_owner,
+        "multisig",
+        &owner.pubkey(),
+    )
+    .await;
+    post_record(&mut ctx, &domain, &owner, "TXT", b"before").await;
+    transfer_domain(&mut ctx, &domain, &owner, &multisig).await;
+    let ix = set_parent_authority_ix(&payer, &domain, &multisig, None, &[signer.pubkey()], true);
+    send(&mut ctx, &[ix], &[&signer]).await.unwrap();
+    let ix = parent_edit(&payer, &domain, &parent, &parent_owner.pubkey(), b"after");
+    send(&mut ctx, &[ix], &[&parent_owner]).await.unwrap();
+
+    let domain = create_subdomain(
+        &mut ctx,
+        &parent,
+        &parent_owner,
+        "tokenized",
+        &owner.pubkey(),
+    )
+    .await;
+    post_record(&mut ctx, &domain, &owner, "TXT", b"before").await;
+    transfer_domain(&mut ctx, &domain, &owner, &get_tokenizer_vault()).await;
+    let token_account = add_token_account(&mut ctx, &get_domain_mint(&domain), &holder.pubkey(), 1);
+    let ix = set_parent_authority_ix(
+        &payer,
+        &domain,
+        &holder.pubkey(),
+        Some(&token_account),
+        &[],
+        true,
+    );
+    send(&mut ctx, &[ix], &[&holder]).await.unwrap();
+    let ix = parent_edit(&payer, &domain, &parent, &parent_owner.pubkey(), b"after");
+    send(&mut ctx, &[ix], &[&parent_owner]).await.unwrap();
+}
+
+/// Builds an edit of the `TXT` record of `domain` by `holder` through `domain_token_account`
+fn holder_edit(
+    payer: &Pubkey,
+    domain: &Pubkey,
+    holder: &Pubkey,
+    domain_token_account: &Pubkey,
+    content: &[u8],
+) -> Instruction {
+    edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: payer,
+            record: &get_record_key_and_seeds(domain, "TXT").0,
+            domain,
+            domain_owner: holder,
+            central_state: &central_state::KEY,
+            config: &Config::find_key(&sns_records::ID).0,
+            domain_token_account: Some(domain_token_account),
+            parent_domain: None,
+            parent_authority: None,
+            multisig_signers: &[],
+        },
+        edit_record::Params {
+            record: "TXT".to_owned(),
+            content: content.to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    )
+}
+
+#[tokio::test]
+async fn token_holder_manages_the_records_of_a_tokenized_domain() {
+    let mut ctx = program_test().start_with_context().await;
+    let owner = Keypair::new();
+    let holder = Keypair::new();
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let record = post_record(&mut ctx, &domain, &owner, "TXT", b"before").await;
+    transfer_domain(&mut ctx, &domain, &owner, &get_tokenizer_vault()).await;
+    let token_account = add_token_account(&mut ctx, &get_domain_mint(&domain), &holder.pubkey(), 1);
+
+    let ix = holder_edit(&payer, &domain, &holder.pubkey(), &token_account, b"after");
+    send(&mut ctx, &[ix], &[&holder]).await.unwrap();
+
+    let ix = validate_solana_signature(
+        validate_solana_signature::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &holder.pubkey(),
+            central_state: &central_state::KEY,
+            verifier: &holder.pubkey(),
+            config: &config,
+            domain_token_account: Some(&token_account),
+            multisig_signers: &[],
+        },
+        validate_solana_signature::Params { staleness: true },
+    );
+    send(&mut ctx, &[ix], &[&holder]).await.unwrap();
+    let data = record_data(&mut ctx, &record).await;
+    let view = RecordView::parse(&data).unwrap();
+    assert_eq!(view.content, b"after");
+    assert_eq!(view.staleness_validation().unwrap(), Validation::Solana);
+    assert_eq!(view.staleness_id, holder.pubkey().as_ref());
+
+    let ix = delete_record(
+        delete_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &holder.pubkey(),
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: Some(&token_account),
+            multisig_signers: &[],
+        },
+        delete_record::Params {},
+    );
+    send(&mut ctx, &[ix], &[&holder]).await.unwrap();
+    assert!(account_data(&mut ctx, &record).await.is_none());
+}
+
+#[tokio::test]
+async fn wrong_token_holders_are_rejected() {
+    let mut ctx = program_test().start_with_context().await;
+    let owner = Keypair::new();
+    let holder = Keypair::new();
+    let payer = ctx.payer.pubkey();
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    post_record(&mut ctx, &domain, &owner, "TXT", b"before").await;
+
+    // A token account of the right mint is not enough while the vault doesn't own the domain
+    let mint = get_domain_mint(&domain);
+    let token_account = add_token_account(&mut ctx, &mint, &holder.pubkey(), 1);
+    let ix = holder_edit(&payer, &domain, &holder.pubkey(), &token_account, b"after");
+    let result = send(&mut ctx, &[ix], &[&holder]).await;
+    assert_error(result, SnsRecordsError::WrongDomainOwner);
+
+    transfer_domain(&mut ctx, &domain, &owner, &get_tokenizer_vault()).await;
+    let wrong_mint = add_token_account(&mut ctx, &Pubkey::new_unique(), &holder.pubkey(), 1);
+    let emptied = add_token_account(&mut ctx, &mint, &holder.pubkey(), 0);
+    let other_holder = add_token_account(&mut ctx, &mint, &Pubkey::new_unique(), 1);
+    for token_account in [wrong_mint, emptied, other_holder] {
+        let ix = holder_edit(&payer, &domain, &holder.pubkey(), &token_account, b"after");
+        let result = send(&mut ctx, &[ix], &[&holder]).await;
+        assert_error(result, SnsRecordsError::WrongTokenHolder);
+    }
+}
+
+/// Builds an edit of the `TXT` record of `domain` owned by `multisig`, signed by `signers`
+fn multisig_edit(
+    payer: &Pubkey,
+    domain: &Pubkey,
+    multisig: &Pubkey,
+    signers: &[Pubkey],
+) -> Instruction {
+    edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: payer,
+            record: &get_record_key_and_seeds(domain, "TXT").0,
+            domain,
+            domain_owner: multisig,
+            central_state: &central_state::KEY,
+            config: &Config::find_key(&sns_records::ID).0,
+            domain_token_account: None,
+            parent_domain: None,
+            parent_authority: None,
+            multisig_signers: signers,
+        },
+        edit_record::Params {
+            record: "TXT".to_owned(),
+            content: b"after".to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    )
+}
+
+/// The builders leave the multisig unsigned, `m` of its signers manage the records
+#[tokio::test]
+async fn multisig_owner_manages_records_through_m_of_n_signers() {
+    let mut ctx = program_test().start_with_context().await;
+    let owner = Keypair::new();
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let keys = signers.iter().map(|s| s.pubkey()).collect::<Vec<_>>();
+    let payer = ctx.payer.pubkey();
+    let config = Config::find_key(&sns_records::ID).0;
+    let multisig = add_multisig(&mut ctx, 2, &keys);
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let record = post_record(&mut ctx, &domain, &owner, "TXT", b"before").await;
+    transfer_domain(&mut ctx, &domain, &owner, &multisig).await;
+
+    let ix = multisig_edit(&payer, &domain, &multisig, &[keys[0], keys[2]]);
+    assert!(!ix
+        .accounts
+        .iter()
+        .any(|a| a.pubkey == multisig && a.is_signer));
+    send(&mut ctx, &[ix], &[&signers[0], &signers[2]])
+        .await
+        .unwrap();
+    let data = record_data(&mut ctx, &record).await;
+    assert_eq!(RecordView::parse(&data).unwrap().content, b"after");
+
+    let ix = delete_record(
+        delete_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &multisig,
+            central_state: &central_state::KEY,
+            config: &config,
+            domain_token_account: None,
+            multisig_signers: &keys[1..],
+        },
+        delete_record::Params {},
+    );
+    send(&mut ctx, &[ix], &[&signers[1], &signers[2]])
+        .await
+        .unwrap();
+    assert!(account_data(&mut ctx, &record).await.is_none());
+}
+
+#[tokio::test]
+async fn multisig_owner_needs_m_distinct_member_signers() {
+    let mut ctx = program_test().start_with_context().await;
+    let owner = Keypair::new();
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let keys = signers.iter().map(|s| s.pubkey()).collect::<Vec<_>>();
+    let outsider = Keypair::new();
+    let payer = ctx.payer.pubkey();
+    let multisig = add_multisig(&mut ctx, 2, &keys);
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    post_record(&mut ctx, &domain, &owner, "TXT", b"before").await;
+    transfer_domain(&mut ctx, &domain, &owner, &multisig).await;
+
+    // Below the threshold
+    let ix = multisig_edit(&payer, &domain, &multisig, &keys[..1]);
+    let result = send(&mut ctx, &[ix], &[&signers[0]]).await;
+    assert_error(result, SnsRecordsError::NotEnoughMultisigSigners);
+
+    // The same signer passed twice counts once
+    let ix = multisig_edit(&payer, &domain, &multisig, &[keys[1], keys[1]]);
+    let result = send(&mut ctx, &[ix], &[&signers[1]]).await;
+    assert_error(result, SnsRecordsError::NotEnoughMultisigSigners);
+
+    // A signer outside of the multisig doesn't count
+    let ix = multisig_edit(&payer, &domain, &multisig, &[keys[2], outsider.pubkey()]);
+    let result = send(&mut ctx, &[ix], &[&signers[2], &outsider]).await;
+    assert_error(result, SnsRecordsError::NotEnoughMultisigSigners);
+}
+
+fn set_attestors(
+    payer: &Pubkey,
+    admin: &Pubkey,
+    threshold: u8,
+    attestors: &[Pubkey],
+) -> Instruction {
+    set_attestor_set(
+        set_attestor_set::Accounts {
+            system_program: &system_program::ID,
+            fee_payer: payer,
+            attestor_set: &AttestorSet::find_key(&sns_records::ID).0,
+            config: &Config::find_key(&sns_records::ID).0,
+            admin,
+        },
+        set_attestor_set::Params {
+            threshold,
+            attestors: attestors.to_vec(),
+        },
+    )
+}
+
+fn attestation(
+    payer: &Pubkey,
+    record: &Pubkey,
+    domain: &Pubkey,
+    domain_owner: &Pubkey,
+    attestors: &[Pubkey],
+    address: [u8; 20],
+) -> Instruction {
+    validate_ethereum_attestation(
+        validate_ethereum_attestation::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: payer,
+            record,
+            domain,
+            domain_owner,
+            central_state: &central_state::KEY,
+            attestor_set: &AttestorSet::find_key(&sns_records::ID).0,
+            config: &Config::find_key(&sns_records::ID).0,
+            domain_token_account: None,
+            attestors,
+        },
+        validate_ethereum_attestation::Params {
+            expected_pubkey: address.to_vec(),
+        },
+    )
+}
+
+#[tokio::test]
+async fn config_admin_manages_the_attestor_set() {
+    let admin = Keypair::new();
+    let mut program_test = program_test();
+    add_config(
+        &mut program_test,
+        &Config {
+            admin: admin.pubkey(),
+            ..Config::default()
+        },
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let payer = ctx.payer.pubkey();
+    let outsider = Keypair::new();
+    let attestors = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let attestor_set = AttestorSet::find_key(&sns_records::ID).0;
+
+    let ix = set_attestors(&payer, &outsider.pubkey(), 1, &attestors);
+    let result = send(&mut ctx, &[ix], &[&outsider]).await;
+    assert_error(result, SnsRecordsError::InvalidAdmin);
+
+    let ix = set_attestors(&payer, &admin.pubkey(), 3, &attestors);
+    let result = send(&mut ctx, &[ix], &[&admin]).await;
+    assert_error(result, SnsRecordsError::InvalidAttestorSet);
+    let ix = set_attestors(&payer, &admin.pubkey(), 1, &[attestors[0], attestors[0]]);
+    let result = send(&mut ctx, &[ix], &[&admin]).await;
+    assert_error(result, SnsRecordsError::InvalidAttestorSet);
+
+    let ix = set_attestors(&payer, &admin.pubkey(), 2, &attestors);
+    send(&mut ctx, &[ix], &[&admin]).await.unwrap();
+    let ix = set_attestors(&payer, &admin.pubkey(), 1, &attestors[1..]);
+    send(&mut ctx, &[ix], &[&admin]).await.unwrap();
+    let data = account_data(&mut ctx, &attestor_set).await.unwrap();
+    assert_eq!(
+        AttestorSet::deserialize(&mut &data[..]).unwrap(),
+        AttestorSet {
+            threshold: 1,
+            attestors: attestors[1..].to_vec(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn attested_roas_need_threshold_distinct_attestors() {
+    let admin = Keypair::new();
+    let mut program_test = program_test();
+    add_config(
+        &mut program_test,
+        &Config {
+            admin: admin.pubkey(),
+            ..Config::default()
+        },
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let payer = ctx.payer.pubkey();
+    let owner = Keypair::new();
+    let attestors = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let keys = attestors.iter().map(|a| a.pubkey()).collect::<Vec<_>>();
+    let outsider = Keypair::new();
+    let address = [0xab; 20];
+
+    let ix = set_attestors(&payer, &admin.pubkey(), 2, &keys);
+    send(&mut ctx, &[ix], &[&admin]).await.unwrap();
+    let domain = create_domain(&mut ctx, "bonfida", &owner.pubkey()).await;
+    let record = post_record(&mut ctx, &domain, &owner, "ETH", &address).await;
+    let ix = validate_solana_signature(
+        validate_solana_signature::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            verifier: &owner.pubkey(),
+            config: &Config::find_key(&sns_records::ID).0,
+            domain_token_account: None,
+            multisig_signers: &[],
+        },
+        validate_solana_signature::Params { staleness: true },
+    );
+    send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+
+    // Below the threshold, the same attestor twice, and an attestor outside of the set
+    let rejected: [(&[Pubkey], &[&Keypair]); 3] = [
+        (&keys[..1], &[&attestors[0]]),
+        (&[keys[1], keys[1]], &[&attestors[1]]),
+        (&[keys[2], outsider.pubkey()], &[&attestors[2], &outsider]),
+    ];
+    for (signers, keypairs) in rejected {
+        let ix = attestation(&payer, &record, &domain, &owner.pubkey(), signers, address);
+        let mut all_signers = vec![&owner];
+        all_signers.extend_from_slice(keypairs);
+        let result = send(&mut ctx, &[ix], &all_signers).await;
+        assert_error(result, SnsRecordsError::NotEnoughAttestors);
+    }
+
+    let ix = attestation(
+        &payer,
+        &record,
+        &domain,
+        &owner.pubkey(),
+        &[keys[0], keys[2]],
+        address,
+    );
+    send(&mut ctx, &[ix], &[&owner, &attestors[0], &attestors[2]])
+        .await
+        .unwrap();
+    let data = record_data(&mut ctx, &record).await;
+    let view = RecordView::parse(&data).unwrap();
+    assert_eq!(
+        view.right_of_association_validation().unwrap(),
+        Validation::EthereumAttested
+    );
+    assert_eq!(view.roa_id, address);
+}
+
+#[tokio::test]
+async fn record_mutations_log_their_event() {
+    let mut ctx = program_test().start_with_context().await;
+    capture_log_data();
+    let owner = Keypair::new();
+    let domain = create_domain(&mut ctx, "logged-events", &owner.pubkey()).await;
+    let record = post_record(&mut ctx, &domain, &owner, "TXT", b"before").await;
+    let payer = ctx.payer.pubkey();
+
+    let ix = edit_record(
+        edit_record::Accounts {
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &payer,
+            record: &record,
+            domain: &domain,
+            domain_owner: &owner.pubkey(),
+            central_state: &central_state::KEY,
+            config: &Config::find_key(&sns_records::ID).0,
+            domain_token_account: None,
+            parent_domain: None,
+            parent_authority: None,
+            multisig_signers: &[],
+        },
+        edit_record::Params {
+            record: "TXT".to_owned(),
+            content: b"after".to_vec(),
+            content_encoding: ContentEncoding::None,
+        },
+    );
+    send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+
+    let events = log_data()
+        .iter()
+        .filter_map(|fields| {
+            let fields = fields.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            RecordEvent::decode(&fields)
+        })
+        .filter(|event| event.record == record)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [
+            (ProgramInstruction::AllocateAndPostRecord, &b"before"[..]),
+            (ProgramInstruction::EditRecord, &b"after"[..]),
+        ]
+        .iter()
+        .map(|(operation, content)| RecordEvent {
+            operation: *operation,
+            record,
+            domain,
+            record_name_hash: Some(get_hashed_name("TXT").try_into().unwrap()),
+            staleness_validation: Validation::None,
+            right_of_association_validation: Validation::None,
+            content_hash: Some(hash(content).to_bytes()),
+        })
+        .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,46 @@
+use std::convert::TryFrom;
+
+use sns_records::{
+    error::SnsRecordsError,
+    record_kind::RecordKind,
+    utils::{get_hashed_name, get_record_key_and_seeds},
+};
+use solana_program::pubkey::Pubkey;
+
+const DOMAIN: Pubkey = Pubkey::new_from_array([1; 32]);
+
+#[test]
+fn every_kind_round_trips() {
+    for (i, kind) in RecordKind::ALL.iter().enumerate() {
+        assert_eq!(*kind as u8 as usize, i);
+        assert_eq!(RecordKind::try_from(*kind as u8).unwrap(), *kind);
+        assert_eq!(kind.as_str().parse::<RecordKind>().unwrap(), *kind);
+
+        let hashed_name = get_hashed_name(kind.as_str());
+        assert_eq!(RecordKind::from_hashed_name(&hashed_name), Some(*kind));
+
+        let (record_key, _) = get_record_key_and_seeds(&DOMAIN, kind.as_str());
+        assert_eq!(
+            RecordKind::from_record_key(&DOMAIN, &record_key),
+            Some(*kind)
+        );
+    }
+}
+
+#[test]
+fn unknown_kinds_are_rejected() {
+    let result = RecordKind::try_from(RecordKind::ALL.len() as u8);
+    assert!(matches!(result, Err(SnsRecordsError::DataTypeMismatch)));
+    let result = "custom".parse::<RecordKind>();
+    assert!(matches!(result, Err(SnsRecordsError::DataTypeMismatch)));
+
+    assert_eq!(
+        RecordKind::from_hashed_name(&get_hashed_name("custom")),
+        None
+    );
+    let (record_key, _) = get_record_key_and_seeds(&DOMAIN, "custom");
+    assert_eq!(RecordKind::from_record_key(&DOMAIN, &record_key), None);
+    // Record keys are bound to their domain
+    let (record_key, _) = get_record_key_and_seeds(&Pubkey::new_from_array([2; 32]), "SOL");
+    assert_eq!(RecordKind::from_record_key(&DOMAIN, &record_key), None);
+}
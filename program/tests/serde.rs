@@ -0,0 +1,363 @@
+#![cfg(feature = "serde")]
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use sns_records::{
+    instruction::*,
+    state::{
+        content_encoding::ContentEncoding,
+        record::{DecodedRecord, RecordView},
+        record_header::RecordHeader,
+        transition,
+        validation::Validation,
+    },
+};
+use solana_program::pubkey::Pubkey;
+use std::convert::TryFrom;
+
+const KEY: Pubkey = Pubkey::new_from_array([1; 32]);
+const ETH_ADDRESS: [u8; 20] = [0xab; 20];
+
+/// Serializes `value`, deserializes it back and checks that nothing was lost on the way
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> Value {
+    let json = serde_json::to_string(value).unwrap();
+    let back: T = serde_json::from_str(&json).unwrap();
+    let value = serde_json::to_value(value).unwrap();
+    assert_eq!(serde_json::to_value(&back).unwrap(), value);
+    value
+}
+
+fn eth_hex() -> String {
+    format!("0x{}", "ab".repeat(20))
+}
+
+#[test]
+fn record_header() {
+    let mut header = RecordHeader::new(42);
+    header.staleness_validation = Validation::Solana as u16;
+    header.right_of_association_validation = Validation::Ethereum as u16;
+    assert_eq!(
+        round_trip(&header),
+        json!({
+            "stalenessValidation": 1,
+            "rightOfAssociationValidation": 2,
+            "contentLength": 42,
+        })
+    );
+}
+
+#[test]
+fn validation() {
+    assert_eq!(round_trip(&Validation::None), json!("None"));
+    assert_eq!(round_trip(&Validation::Solana), json!("Solana"));
+    assert_eq!(
+        round_trip(&Validation::EthereumAttested),
+        json!("EthereumAttested")
+    );
+    assert!(serde_json::from_value::<Validation>(json!("Bitcoin")).is_err());
+}
+
+#[test]
+fn decoded_record() {
+    let data = transition::new_record(&ETH_ADDRESS, ContentEncoding::None).unwrap();
+    let data = transition::validate_staleness(&data, Validation::Solana, KEY.as_ref()).unwrap();
+    let mut record = RecordView::parse(&data).unwrap();
+    record.header.right_of_association_validation = Validation::Ethereum as u16;
+    record.roa_id = &ETH_ADDRESS;
+    let record = DecodedRecord::try_from(record).unwrap();
+
+    // Solana ids are base58, other ids are 0x prefixed hex, binary content is hex
+    let json = round_trip(&record);
+    assert_eq!(
+        json,
+        json!({
+            "stalenessValidation": "Solana",
+            "stalenessId": KEY.to_string(),
+            "rightOfAssociationValidation": "Ethereum",
+            "roaId": eth_hex(),
+            "contentEncoding": "None",
+            "content": { "hex": "ab".repeat(20) },
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<DecodedRecord>(json).unwrap(),
+        record
+    );
+
+    // UTF-8 content is kept readable, ids are omitted without validation
+    let data = transition::new_record("bonfida.sol ✓".as_bytes(), ContentEncoding::None).unwrap();
+    let record = DecodedRecord::try_from(RecordView::parse(&data).unwrap()).unwrap();
+    assert_eq!(
+        round_trip(&record),
+        json!({
+            "stalenessValidation": "None",
+            "stalenessId": null,
+            "rightOfAssociationValidation": "None",
+            "roaId": null,
+            "contentEncoding": "None",
+            "content": { "utf8": "bonfida.sol ✓" },
+        })
+    );
+}
+
+#[test]
+fn decoded_record_rejects_inconsistent_ids() {
+    let json = json!({
+        "stalenessValidation": "Solana",
+        "stalenessId": null,
+        "rightOfAssociationValidation": "None",
+        "roaId": null,
+        "content": { "utf8": "" },
+    });
+    assert!(serde_json::from_value::<DecodedRecord>(json).is_err());
+
+    let json = json!({
+        "stalenessValidation": "None",
+        "stalenessId": null,
+        "rightOfAssociationValidation": "Ethereum",
+        "roaId": "0xzz",
+        "content": { "utf8": "" },
+    });
+    assert!(serde_json::from_value::<DecodedRecord>(json).is_err());
+}
+
+#[test]
+fn record_params() {
+    let params = allocate_record::Params {
+        content_length: 32,
+        record: "SOL".to_owned(),
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({ "contentLength": 32, "record": "SOL" })
+    );
+
+    let params = allocate_and_post_record::Params {
+        record: "url".to_owned(),
+        content: b"https://sns.id".to_vec(),
+        content_encoding: ContentEncoding::None,
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({
+            "record": "url",
+            "content": { "utf8": "https://sns.id" },
+            "contentEncoding": "None",
+        })
+    );
+
+    let params = edit_record::Params {
+        record: "pic".to_owned(),
+        content: vec![0x78, 0x9c, 0xff],
+        content_encoding: ContentEncoding::Deflate,
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({
+            "record": "pic",
+            "content": { "hex": "789cff" },
+            "contentEncoding": "Deflate",
+        })
+    );
+
+    assert_eq!(round_trip(&delete_record::Params {}), json!({}));
+    assert_eq!(round_trip(&unverify_roa::Params {}), json!({}));
+    assert_eq!(round_trip(&refresh_staleness::Params {}), json!({}));
+    assert_eq!(round_trip(&finalize_record::Params {}), json!({}));
+
+    let params = allocate_chunked_record::Params {
+        record: "pic".to_owned(),
+        content_length: 2048,
+        content_hash: vec![0xee; 32],
+        content_encoding: ContentEncoding::Deflate,
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({
+            "record": "pic",
+            "contentLength": 2048,
+            "contentHash": format!("0x{}", "ee".repeat(32)),
+            "contentEncoding": "Deflate",
+        })
+    );
+
+    let params = write_record_chunk::Params {
+        offset: 1024,
+        chunk: vec![0, 1, 2],
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({ "offset": 1024, "chunk": "0x000102" })
+    );
+}
+
+#[test]
+fn validation_params() {
+    assert_eq!(
+        round_trip(&validate_solana_signature::Params { staleness: true }),
+        json!({ "staleness": true })
+    );
+
+    let params = validate_ethereum_signature::Params {
+        validation: Validation::Ethereum,
+        signature: vec![0x1b; 65],
+        expected_pubkey: ETH_ADDRESS.to_vec(),
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({
+            "validation": "Ethereum",
+            "signature": format!("0x{}", "1b".repeat(65)),
+            "expectedPubkey": eth_hex(),
+        })
+    );
+
+    // The RoA id of a Solana verifier is base58
+    let params = write_roa::Params {
+        roa_id: KEY.to_bytes().to_vec(),
+    };
+    assert_eq!(round_trip(&params), json!({ "roaId": KEY.to_string() }));
+
+    let params = validate_secp256r1_signature::Params {
+        authenticator_data: vec![0x49; 37],
+        client_data_json: br#"{"type":"webauthn.get"}"#.to_vec(),
+        public_key: vec![0x02; 33],
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({
+            "authenticatorData": format!("0x{}", "49".repeat(37)),
+            "clientDataJson": { "utf8": r#"{"type":"webauthn.get"}"# },
+            "publicKey": format!("0x{}", "02".repeat(33)),
+        })
+    );
+
+    let params = validate_cosmos_signature::Params {
+        address: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_owned(),
+        signature: vec![0x11; 64],
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({
+            "address": "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu",
+            "signature": format!("0x{}", "11".repeat(64)),
+        })
+    );
+
+    let params = validate_sui_signature::Params {
+        public_key: vec![0x22; 32],
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({ "publicKey": format!("0x{}", "22".repeat(32)) })
+    );
+
+    let params = validate_aptos_signature::Params {
+        public_key: vec![0x33; 32],
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({ "publicKey": format!("0x{}", "33".repeat(32)) })
+    );
+
+    let params = validate_ethereum_attestation::Params {
+        expected_pubkey: ETH_ADDRESS.to_vec(),
+    };
+    assert_eq!(round_trip(&params), json!({ "expectedPubkey": eth_hex() }));
+}
+
+#[test]
+fn entry_params() {
+    let params = add_record_entry::Params {
+        record: "ETH".to_owned(),
+        entry: ETH_ADDRESS.to_vec(),
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({ "record": "ETH", "entry": { "hex": "ab".repeat(20) } })
+    );
+
+    assert_eq!(
+        round_trip(&remove_record_entry::Params { index: 3 }),
+        json!({ "index": 3 })
+    );
+    assert_eq!(
+        round_trip(&unverify_entry::Params { index: 4 }),
+        json!({ "index": 4 })
+    );
+    assert_eq!(
+        round_trip(&validate_entry_solana_signature::Params { index: 5 }),
+        json!({ "index": 5 })
+    );
+
+    let params = validate_entry_ethereum_signature::Params {
+        index: 6,
+        signature: vec![0x1c; 65],
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({ "index": 6, "signature": format!("0x{}", "1c".repeat(65)) })
+    );
+}
+
+#[test]
+fn admin_params() {
+    let params = init_config::Params {
+        admin: KEY,
+        paused: false,
+        paused_instructions: 1 << 2,
+        enabled_validations: u64::MAX,
+        max_content_length: 10_000,
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({
+            "admin": KEY.to_string(),
+            "paused": false,
+            "pausedInstructions": 4,
+            "enabledValidations": u64::MAX,
+            "maxContentLength": 10_000,
+        })
+    );
+
+    let params = update_config::Params {
+        admin: None,
+        paused: Some(true),
+        paused_instructions: None,
+        enabled_validations: None,
+        max_content_length: Some(512),
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({
+            "admin": null,
+            "paused": true,
+            "pausedInstructions": null,
+            "enabledValidations": null,
+            "maxContentLength": 512,
+        })
+    );
+    let params = update_config::Params {
+        admin: Some(KEY),
+        ..params
+    };
+    assert_eq!(round_trip(&params)["admin"], json!(KEY.to_string()));
+
+    let attestor = Pubkey::new_from_array([2; 32]);
+    let params = set_attestor_set::Params {
+        threshold: 2,
+        attestors: vec![KEY, attestor],
+    };
+    assert_eq!(
+        round_trip(&params),
+        json!({
+            "threshold": 2,
+            "attestors": [KEY.to_string(), attestor.to_string()],
+        })
+    );
+
+    assert_eq!(
+        round_trip(&set_parent_authority::Params { enabled: true }),
+        json!({ "enabled": true })
+    );
+}
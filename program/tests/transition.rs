@@ -0,0 +1,783 @@
+mod common;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use miniz_oxide::deflate::compress_to_vec;
+use sns_records::{
+    crypto::{bech32_decode, bech32_encode},
+    error::SnsRecordsError,
+    state::{
+        content_encoding::{ContentEncoding, MAX_DECODED_LENGTH},
+        message::{
+            adr36_sign_doc, aptos_signed_message, message_to_sign, record_message,
+            sui_signed_message, webauthn_challenge, WEBAUTHN_RP_ID,
+        },
+        record::RecordView,
+        record_list::{self, RecordEntry},
+        transition,
+        validation::Validation,
+    },
+};
+use solana_program::{hash::hash, pubkey::Pubkey};
+
+const RECORD_KEY: Pubkey = Pubkey::new_from_array([1; 32]);
+const DOMAIN_OWNER: Pubkey = Pubkey::new_from_array([2; 32]);
+const VERIFIER: Pubkey = Pubkey::new_from_array([3; 32]);
+
+/// The address of the `0x11..11` private key, which signed `ETH_SIGNATURE`
+const ETH_ADDRESS: &str = "19e7e376e7c213b7e7e7e46cc70a5dd086daff2a";
+/// Signature of the `message_to_sign` of `ETH_ADDRESS` for `RECORD_KEY` and `DOMAIN_OWNER`
+const ETH_SIGNATURE: &str = "ef9effa9c210e51f4ee2ffc20cf5a874190668b7b03a2d6fbbc783878791cf76\
+                             6aacd598aa795dd6d859e60a425960243ec39152c0d7d8b4bb4a0d23cad8bbfa1c";
+
+fn fresh(content: &[u8]) -> Vec<u8> {
+    transition::new_record(content, ContentEncoding::None).unwrap()
+}
+
+fn stamped(content: &[u8]) -> Vec<u8> {
+    transition::validate_staleness(&fresh(content), Validation::Solana, DOMAIN_OWNER.as_ref())
+        .unwrap()
+}
+
+fn view(data: &[u8]) -> RecordView<'_> {
+    RecordView::parse(data).unwrap()
+}
+
+#[test]
+fn new_record_has_no_staleness_nor_roa() {
+    let data = fresh(b"bonfida.sol");
+    let record = view(&data);
+    assert_eq!(record.staleness_validation().unwrap(), Validation::None);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::None
+    );
+    assert_eq!(record.content, b"bonfida.sol");
+    assert_eq!(record.header.content_length(), 11);
+}
+
+#[test]
+fn edit_resets_staleness_and_roa() {
+    let data = transition::write_roa(&stamped(b"old"), VERIFIER.as_ref()).unwrap();
+    let data = transition::validate_solana_roa(&data, &VERIFIER).unwrap();
+    assert_eq!(
+        view(&data).right_of_association_validation().unwrap(),
+        Validation::Solana
+    );
+
+    // Editing serializes a fresh record, whatever the previous state
+    let edited = fresh(b"new content");
+    let record = view(&edited);
+    assert!(record.staleness_id.is_empty() && record.roa_id.is_empty());
+    assert_eq!(record.content, b"new content");
+}
+
+#[test]
+fn write_roa_writes_unverified_solana_roa() {
+    let data = transition::write_roa(&stamped(b"content"), VERIFIER.as_ref()).unwrap();
+    let record = view(&data);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::UnverifiedSolana
+    );
+    assert_eq!(record.roa_id, VERIFIER.as_ref());
+    assert_eq!(record.staleness_id, DOMAIN_OWNER.as_ref());
+    assert_eq!(record.content, b"content");
+}
+
+#[test]
+fn write_roa_rejects_bad_id_length() {
+    let result = transition::write_roa(&fresh(b"content"), &[3; 20]);
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+}
+
+#[test]
+fn unverify_roa_removes_roa() {
+    let data = transition::write_roa(&stamped(b"content"), VERIFIER.as_ref()).unwrap();
+    let data = transition::unverify_roa(&data, &VERIFIER).unwrap();
+    let record = view(&data);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::None
+    );
+    assert!(record.roa_id.is_empty());
+    assert_eq!(record.content, b"content");
+}
+
+#[test]
+fn unverify_roa_rejects_other_verifier() {
+    let data = transition::write_roa(&stamped(b"content"), VERIFIER.as_ref()).unwrap();
+    let result = transition::unverify_roa(&data, &DOMAIN_OWNER);
+    assert!(matches!(result, Err(SnsRecordsError::InvalidVerifier)));
+}
+
+#[test]
+fn validate_staleness_stamps_id_and_drops_roa() {
+    let data = transition::write_roa(&stamped(b"content"), VERIFIER.as_ref()).unwrap();
+    let data = transition::validate_solana_roa(&data, &VERIFIER).unwrap();
+
+    let new_owner = Pubkey::new_unique();
+    let data =
+        transition::validate_staleness(&data, Validation::Solana, new_owner.as_ref()).unwrap();
+    let record = view(&data);
+    assert_eq!(record.staleness_validation().unwrap(), Validation::Solana);
+    assert_eq!(record.staleness_id, new_owner.as_ref());
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::None
+    );
+    assert!(record.roa_id.is_empty());
+    assert_eq!(record.content, b"content");
+}
+
+#[test]
+fn validate_staleness_rejects_bad_id_length() {
+    let result = transition::validate_staleness(&fresh(b"content"), Validation::Solana, &[2; 31]);
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+
+    let result = transition::validate_staleness(&fresh(b"content"), Validation::XChain, &[2; 32]);
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+}
+
+#[test]
+fn validate_solana_roa_verifies_written_roa() {
+    let data = transition::write_roa(&stamped(b"content"), VERIFIER.as_ref()).unwrap();
+    let data = transition::validate_solana_roa(&data, &VERIFIER).unwrap();
+    let record = view(&data);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::Solana
+    );
+    assert_eq!(record.roa_id, VERIFIER.as_ref());
+}
+
+#[test]
+fn validate_solana_roa_rejects_other_verifier() {
+    let data = transition::write_roa(&stamped(b"content"), VERIFIER.as_ref()).unwrap();
+    let result = transition::validate_solana_roa(&data, &DOMAIN_OWNER);
+    assert!(matches!(result, Err(SnsRecordsError::InvalidVerifier)));
+
+    // Without any RoA written
+    let result = transition::validate_solana_roa(&stamped(b"content"), &VERIFIER);
+    assert!(matches!(result, Err(SnsRecordsError::InvalidVerifier)));
+}
+
+#[test]
+fn validate_ethereum_roa_accepts_known_signature() {
+    let address = hex::decode(ETH_ADDRESS).unwrap();
+    let signature = hex::decode(ETH_SIGNATURE).unwrap();
+    let data =
+        transition::validate_ethereum_roa(&stamped(&address), &RECORD_KEY, &signature, &address)
+            .unwrap();
+    let record = view(&data);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::Ethereum
+    );
+    assert_eq!(record.roa_id, &address[..]);
+    assert_eq!(record.staleness_id, DOMAIN_OWNER.as_ref());
+}
+
+#[test]
+fn validate_ethereum_roa_rejects_wrong_message() {
+    let address = hex::decode(ETH_ADDRESS).unwrap();
+    let signature = hex::decode(ETH_SIGNATURE).unwrap();
+
+    // Another record key, content or staleness id changes the signed message
+    let result =
+        transition::validate_ethereum_roa(&stamped(&address), &VERIFIER, &signature, &address);
+    assert!(matches!(result, Err(SnsRecordsError::EthPubkeyMismatch)));
+
+    let result =
+        transition::validate_ethereum_roa(&stamped(b"other"), &RECORD_KEY, &signature, &address);
+    assert!(matches!(result, Err(SnsRecordsError::EthPubkeyMismatch)));
+
+    let data =
+        transition::validate_staleness(&fresh(&address), Validation::Solana, VERIFIER.as_ref())
+            .unwrap();
+    let result = transition::validate_ethereum_roa(&data, &RECORD_KEY, &signature, &address);
+    assert!(matches!(result, Err(SnsRecordsError::EthPubkeyMismatch)));
+}
+
+#[test]
+fn validate_ethereum_roa_requires_staleness() {
+    let address = hex::decode(ETH_ADDRESS).unwrap();
+    let signature = hex::decode(ETH_SIGNATURE).unwrap();
+    let result =
+        transition::validate_ethereum_roa(&fresh(&address), &RECORD_KEY, &signature, &address);
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+}
+
+#[test]
+fn validate_ethereum_roa_rejects_truncated_signature() {
+    let address = hex::decode(ETH_ADDRESS).unwrap();
+    let signature = hex::decode(ETH_SIGNATURE).unwrap();
+    let result = transition::validate_ethereum_roa(
+        &stamped(&address),
+        &RECORD_KEY,
+        &signature[..64],
+        &address,
+    );
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+}
+
+/// A stamped record being uploaded in chunks, `content` is the final content
+fn incomplete(content: &[u8]) -> Vec<u8> {
+    let data = transition::new_chunked_record(
+        content.len() as u32,
+        hash(content).as_ref(),
+        ContentEncoding::None,
+    )
+    .unwrap();
+    transition::validate_staleness(&data, Validation::Solana, DOMAIN_OWNER.as_ref()).unwrap()
+}
+
+#[test]
+fn roa_cannot_be_validated_while_uploading() {
+    let address = hex::decode(ETH_ADDRESS).unwrap();
+    let signature = hex::decode(ETH_SIGNATURE).unwrap();
+    let data = incomplete(&address);
+
+    let result = transition::write_roa(&data, VERIFIER.as_ref());
+    assert!(matches!(result, Err(SnsRecordsError::UploadInProgress)));
+    let result = transition::validate_solana_roa(&data, &VERIFIER);
+    assert!(matches!(result, Err(SnsRecordsError::UploadInProgress)));
+    let result = transition::validate_ethereum_roa(&data, &RECORD_KEY, &signature, &address);
+    assert!(matches!(result, Err(SnsRecordsError::UploadInProgress)));
+    let result = transition::validate_ethereum_attested_roa(&data, &address);
+    assert!(matches!(result, Err(SnsRecordsError::UploadInProgress)));
+    let result = transition::validate_sui_roa(&data, &[4; 32]);
+    assert!(matches!(result, Err(SnsRecordsError::UploadInProgress)));
+    let result = transition::validate_aptos_roa(&data, &[4; 32]);
+    assert!(matches!(result, Err(SnsRecordsError::UploadInProgress)));
+
+    // Once finalized the content is final and the RoA can be validated
+    let data = transition::write_chunk(&data, 0, &address).unwrap();
+    let data = transition::finalize_record(&data).unwrap();
+    transition::validate_ethereum_roa(&data, &RECORD_KEY, &signature, &address).unwrap();
+}
+
+#[test]
+fn write_chunk_removes_roa() {
+    let data = incomplete(b"final content");
+    let mut record = view(&data);
+    record.header.right_of_association_validation = Validation::Solana as u16;
+    record.roa_id = VERIFIER.as_ref();
+    let data = record.to_bytes().unwrap();
+
+    let data = transition::write_chunk(&data, 6, b"content").unwrap();
+    let record = view(&data);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::None
+    );
+    assert!(record.roa_id.is_empty());
+    assert_eq!(record.staleness_id, DOMAIN_OWNER.as_ref());
+    assert_eq!(&record.content[6..13], b"content");
+
+    let data = transition::write_chunk(&data, 0, b"final ").unwrap();
+    let data = transition::finalize_record(&data).unwrap();
+    let record = view(&data);
+    assert_eq!(record.content, b"final content");
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::None
+    );
+}
+
+#[test]
+fn deflate_content_is_checked_when_written() {
+    let stream = compress_to_vec(b"https://sns.id", 6);
+    let data = transition::new_record(&stream, ContentEncoding::Deflate).unwrap();
+    let record = view(&data);
+    assert_eq!(
+        record.decoded_content().unwrap().as_ref(),
+        b"https://sns.id"
+    );
+    // Validation messages commit to the decoded content
+    assert_eq!(
+        record.committed_content().unwrap().as_ref(),
+        b"https://sns.id"
+    );
+
+    let mut trailing = stream.clone();
+    trailing.push(0);
+    let invalid = [&b"not deflate"[..], &stream[..stream.len() - 1], &trailing];
+    for content in invalid.iter() {
+        let result = transition::new_record(content, ContentEncoding::Deflate);
+        assert!(matches!(
+            result,
+            Err(SnsRecordsError::InvalidContentEncoding)
+        ));
+    }
+
+    // The decoded length is bounded
+    let max = compress_to_vec(&[0; MAX_DECODED_LENGTH], 6);
+    assert!(transition::new_record(&max, ContentEncoding::Deflate).is_ok());
+    let bomb = compress_to_vec(&[0; MAX_DECODED_LENGTH + 1], 6);
+    let result = transition::new_record(&bomb, ContentEncoding::Deflate);
+    assert!(matches!(
+        result,
+        Err(SnsRecordsError::InvalidContentEncoding)
+    ));
+}
+
+/// The RoA signed for the plain content validates the same content however it is compressed
+#[test]
+fn deflate_content_keeps_the_roa_of_the_decoded_content() {
+    let address = hex::decode(ETH_ADDRESS).unwrap();
+    let signature = hex::decode(ETH_SIGNATURE).unwrap();
+    for level in [0, 1, 10].iter() {
+        let stream = compress_to_vec(&address, *level);
+        let data = transition::new_record(&stream, ContentEncoding::Deflate).unwrap();
+        let data = transition::validate_staleness(&data, Validation::Solana, DOMAIN_OWNER.as_ref())
+            .unwrap();
+        let data =
+            transition::validate_ethereum_roa(&data, &RECORD_KEY, &signature, &address).unwrap();
+        let record = view(&data);
+        assert_eq!(record.content, &stream[..]);
+        assert_eq!(record.roa_id, &address[..]);
+    }
+}
+
+/// The authenticator data of an assertion: `rpIdHash | flags | signCount`, `0x05` flags are
+/// user present and user verified as set by platform authenticators
+fn authenticator_data(rp_id: &str, flags: u8) -> Vec<u8> {
+    let mut data = hash(rp_id.as_bytes()).to_bytes().to_vec();
+    data.push(flags);
+    data.extend_from_slice(&7u32.to_be_bytes());
+    data
+}
+
+/// The `clientDataJSON` of a `navigator.credentials.get` call, as serialized by browsers
+fn client_data_json(kind: &str, challenge: &[u8]) -> Vec<u8> {
+    format!(
+        r#"{{"type":"{}","challenge":"{}","origin":"https://{}","crossOrigin":false}}"#,
+        kind,
+        URL_SAFE_NO_PAD.encode(challenge),
+        WEBAUTHN_RP_ID
+    )
+    .into_bytes()
+}
+
+#[test]
+fn validate_secp256r1_roa_checks_the_assertion() {
+    let data = stamped(b"content");
+    let public_key = [[0x02].as_ref(), &[0x42; 32]].concat();
+    let challenge = webauthn_challenge(b"content", &RECORD_KEY, &DOMAIN_OWNER);
+    let validate = |authenticator_data: &[u8], client_data_json: &[u8]| {
+        transition::validate_secp256r1_roa(
+            &data,
+            &RECORD_KEY,
+            authenticator_data,
+            client_data_json,
+            &public_key,
+        )
+    };
+
+    let validated = validate(
+        &authenticator_data(WEBAUTHN_RP_ID, 0x05),
+        &client_data_json("webauthn.get", &challenge),
+    )
+    .unwrap();
+    let record = view(&validated);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::Secp256r1
+    );
+    assert_eq!(record.roa_id, &public_key[..]);
+
+    let rejected = [
+        // Another relying party
+        (
+            authenticator_data("evil.com", 0x05),
+            client_data_json("webauthn.get", &challenge),
+        ),
+        // User not present
+        (
+            authenticator_data(WEBAUTHN_RP_ID, 0x04),
+            client_data_json("webauthn.get", &challenge),
+        ),
+        // Truncated sign count
+        (
+            authenticator_data(WEBAUTHN_RP_ID, 0x05)[..35].to_vec(),
+            client_data_json("webauthn.get", &challenge),
+        ),
+        // A registration ceremony
+        (
+            authenticator_data(WEBAUTHN_RP_ID, 0x05),
+            client_data_json("webauthn.create", &challenge),
+        ),
+        // The challenge of another record
+        (
+            authenticator_data(WEBAUTHN_RP_ID, 0x05),
+            client_data_json(
+                "webauthn.get",
+                &webauthn_challenge(b"content", &VERIFIER, &DOMAIN_OWNER),
+            ),
+        ),
+    ];
+    for (authenticator_data, client_data_json) in rejected.iter() {
+        let result = validate(authenticator_data, client_data_json);
+        assert!(matches!(
+            result,
+            Err(SnsRecordsError::InvalidWebAuthnAssertion)
+        ));
+    }
+}
+
+/// ADR-36 `signArbitrary` signatures by the `0x22..22` private key of the `record_message` of
+/// the address itself, for `RECORD_KEY` and `DOMAIN_OWNER`
+///
+/// A `secp256k1` account signs the SHA-256 of the sign doc, an `ethsecp256k1` (Injective)
+/// account its Keccak-256.
+const COSMOS_ADDRESS: &str = "cosmos12vfxp232rx0z9rzn0hay9jptagk8c86dn5t56m";
+const COSMOS_SIGNATURE: &str = "08dfe45da5fec6c98e949a852439292d0a4fc159a98112cd4261cbd16fa5c8c4\
+                                75ec778653a91cf786ec9cd596d0e25e158f86b29166b38c12bb3e011b97f4e8";
+const INJECTIVE_ADDRESS: &str = "inj1z43ezhsefkx0hgv5x4cxq0mkq633z4ggnhuqpg";
+const INJECTIVE_SIGNATURE: &str = "a933b3a3f1f990c6f3a80273e4d9cb5be9f961147f2acef1c31e182c97177a1b\
+                                   53842d619747adc794ddf8a1ccfba4095c99daf6787d8497d7e9fab732459bef";
+
+#[test]
+fn adr36_sign_doc_matches_the_amino_json() {
+    let message = record_message(COSMOS_ADDRESS.as_bytes(), &RECORD_KEY, &DOMAIN_OWNER);
+    let sign_doc = adr36_sign_doc(COSMOS_ADDRESS, &message);
+    assert_eq!(
+        std::str::from_utf8(&sign_doc).unwrap(),
+        concat!(
+            r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","#,
+            r#""msgs":[{"type":"sign/MsgSignData","value":{"data":""#,
+            "NjM2ZjczNmQ2ZjczMzEzMjc2NjY3ODcwMzIzMzMyNzI3ODMwN2EzOTcyN2E2ZTMwNjg2MTc5Mzk2YTcw",
+            "NzQ2MTY3NmIzODYzMzgzNjY0NmUzNTc0MzUzNjZkCkZvciByZWNvcmQ6IDR2SjlKVTFiSkpFOTZGV1NK",
+            "S3ZIc21tRkFEQ2c0Z3BaUWZmNFAzYmtMS2kKU3RhbGVuZXNzIElEOiA4cWJIYncyQmJiVEhCVzFzYmVx",
+            "YWtZWFZLUlFNOE5lN3BMSzdtNkNWZmVS",
+            r#"","signer":"cosmos12vfxp232rx0z9rzn0hay9jptagk8c86dn5t56m"}}],"sequence":"0"}"#,
+        )
+    );
+}
+
+#[test]
+fn validate_cosmos_roa_accepts_secp256k1_signature() {
+    let signature = hex::decode(COSMOS_SIGNATURE).unwrap();
+    let data = stamped(COSMOS_ADDRESS.as_bytes());
+    let validated =
+        transition::validate_cosmos_roa(&data, &RECORD_KEY, COSMOS_ADDRESS, &signature).unwrap();
+    let record = view(&validated);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::Cosmos
+    );
+    assert_eq!(record.roa_id, &bech32_decode(COSMOS_ADDRESS).unwrap().1[..]);
+
+    // The uppercase form of the address is the same account
+    transition::validate_cosmos_roa(
+        &data,
+        &RECORD_KEY,
+        &COSMOS_ADDRESS.to_ascii_uppercase(),
+        &signature,
+    )
+    .unwrap();
+
+    let result = transition::validate_cosmos_roa(&data, &VERIFIER, COSMOS_ADDRESS, &signature);
+    assert!(matches!(
+        result,
+        Err(SnsRecordsError::CosmosAddressMismatch)
+    ));
+}
+
+#[test]
+fn validate_cosmos_roa_accepts_ethsecp256k1_signature() {
+    let signature = hex::decode(INJECTIVE_SIGNATURE).unwrap();
+    let data = stamped(INJECTIVE_ADDRESS.as_bytes());
+    let validated =
+        transition::validate_cosmos_roa(&data, &RECORD_KEY, INJECTIVE_ADDRESS, &signature).unwrap();
+    let record = view(&validated);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::Cosmos
+    );
+    // The account hash is the Ethereum address of the key
+    assert_eq!(
+        hex::encode(record.roa_id),
+        "1563915e194d8cfba1943570603f7606a3115508"
+    );
+}
+
+#[test]
+fn validate_cosmos_roa_binds_digest_to_key_type() {
+    // The same key signing the SHA-256 of the sign doc does not validate its `ethsecp256k1`
+    // account, and the other way around
+    let inj_data = stamped(INJECTIVE_ADDRESS.as_bytes());
+    let cosmos_signature = hex::decode(COSMOS_SIGNATURE).unwrap();
+    let result = transition::validate_cosmos_roa(
+        &inj_data,
+        &RECORD_KEY,
+        INJECTIVE_ADDRESS,
+        &cosmos_signature,
+    );
+    assert!(matches!(
+        result,
+        Err(SnsRecordsError::CosmosAddressMismatch)
+    ));
+
+    let cosmos_data = stamped(COSMOS_ADDRESS.as_bytes());
+    let inj_signature = hex::decode(INJECTIVE_SIGNATURE).unwrap();
+    let result =
+        transition::validate_cosmos_roa(&cosmos_data, &RECORD_KEY, COSMOS_ADDRESS, &inj_signature);
+    assert!(matches!(
+        result,
+        Err(SnsRecordsError::CosmosAddressMismatch)
+    ));
+
+    let result = transition::validate_cosmos_roa(
+        &cosmos_data,
+        &RECORD_KEY,
+        "cosmos12vfxp232rx0z9rzn0hay9jptagk8c86dn5t56n",
+        &cosmos_signature,
+    );
+    assert!(matches!(result, Err(SnsRecordsError::InvalidCosmosAddress)));
+}
+
+#[test]
+fn validate_cosmos_roa_matches_the_record_content() {
+    let signature = hex::decode(COSMOS_SIGNATURE).unwrap();
+    let data = stamped(INJECTIVE_ADDRESS.as_bytes());
+    let result = transition::validate_cosmos_roa(&data, &RECORD_KEY, COSMOS_ADDRESS, &signature);
+    assert!(matches!(result, Err(SnsRecordsError::AddressMismatch)));
+
+    // The same account on another chain is another address
+    let (_, account_hash) = bech32_decode(COSMOS_ADDRESS).unwrap();
+    let data = stamped(bech32_encode("osmo", &account_hash).as_bytes());
+    let result = transition::validate_cosmos_roa(&data, &RECORD_KEY, COSMOS_ADDRESS, &signature);
+    assert!(matches!(result, Err(SnsRecordsError::AddressMismatch)));
+
+    // Addresses are only matched against unencoded content
+    let deflated = transition::new_record(
+        &compress_to_vec(COSMOS_ADDRESS.as_bytes(), 6),
+        ContentEncoding::Deflate,
+    )
+    .unwrap();
+    let data = transition::validate_staleness(&deflated, Validation::Solana, DOMAIN_OWNER.as_ref())
+        .unwrap();
+    let result = transition::validate_cosmos_roa(&data, &RECORD_KEY, COSMOS_ADDRESS, &signature);
+    assert!(matches!(
+        result,
+        Err(SnsRecordsError::InvalidContentEncoding)
+    ));
+}
+
+/// The Ed25519 public key of the `0x33..33` secret key, which produced the Sui and Aptos
+/// signatures below for `RECORD_KEY` and `DOMAIN_OWNER`
+const ED25519_PUBKEY: &str = "17cb79fb2b4120f2b1ec65e4198d6e08b28e813feb01e4a400839b85e18080ce";
+const SUI_ADDRESS: &str = "0x11fd1b265883bba07e9e04a95997603dadaf15738badbe9070fa5e06675df497";
+/// `signPersonalMessage` signature of the record message of `SUI_ADDRESS`
+const SUI_SIGNATURE: &str = "846d9cc492cb02cff6b96bc422373267bae6c1501dcfd9c7d3dd8da1007485e6\
+                             6a2ddb6a68cb10f5772e25992b4ce186064f85fd88d5b38283b64b1fc48edb0e";
+const APTOS_ADDRESS: &str = "0x121f5dc2e67b1c62df700496c9704904f45eac6ddf458452dbeef1cabdf4709f";
+/// `signMessage` signature of the record message of `APTOS_ADDRESS`, with the staleness id as
+/// nonce
+const APTOS_SIGNATURE: &str = "f6001eef69e3e2ffa69dc6493180b821f7b1867350b53012a9768c8df2c1f026\
+                               7f565b317cce6059848c578c8c3e4c7ca7ee0bce67cb83a41ccdaaa59c394208";
+
+fn ed25519_verify(message: &[u8], signature: &str) {
+    let public_key =
+        ed25519_dalek::PublicKey::from_bytes(&hex::decode(ED25519_PUBKEY).unwrap()).unwrap();
+    let signature = ed25519_dalek::Signature::from_bytes(&hex::decode(signature).unwrap()).unwrap();
+    public_key.verify_strict(message, &signature).unwrap();
+}
+
+#[test]
+fn sui_signed_message_matches_personal_message_digest() {
+    // blake2b256([3, 0, 0] | uleb128(246) | message), the message is longer than 127 bytes
+    let digest = sui_signed_message(SUI_ADDRESS.as_bytes(), &RECORD_KEY, &DOMAIN_OWNER);
+    assert_eq!(
+        hex::encode(&digest),
+        "5119e9b6330ad5b5f76d4d61b1aed36e2204aa476a4b3060ae27d934f1011f26"
+    );
+    ed25519_verify(&digest, SUI_SIGNATURE);
+}
+
+#[test]
+fn validate_sui_roa_derives_the_address() {
+    let public_key = hex::decode(ED25519_PUBKEY).unwrap();
+    let data = transition::validate_sui_roa(&stamped(SUI_ADDRESS.as_bytes()), &public_key).unwrap();
+    let record = view(&data);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::Sui
+    );
+    assert_eq!(record.roa_id, &hex::decode(&SUI_ADDRESS[2..]).unwrap()[..]);
+
+    // The Aptos address of the same key is another account
+    let result = transition::validate_sui_roa(&stamped(APTOS_ADDRESS.as_bytes()), &public_key);
+    assert!(matches!(result, Err(SnsRecordsError::AddressMismatch)));
+}
+
+#[test]
+fn aptos_signed_message_matches_sign_message_format() {
+    let message = aptos_signed_message(APTOS_ADDRESS.as_bytes(), &RECORD_KEY, &DOMAIN_OWNER);
+    assert_eq!(
+        std::str::from_utf8(&message).unwrap(),
+        concat!(
+            "APTOS\nmessage: ",
+            "3078313231663564633265363762316336326466373030343936633937303439",
+            "3034663435656163366464663435383435326462656566316361626466343730",
+            "3966\n",
+            "For record: 4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi\n",
+            "Staleness ID: 8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR\n",
+            "nonce: 8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR",
+        )
+    );
+    ed25519_verify(&message, APTOS_SIGNATURE);
+}
+
+#[test]
+fn validate_aptos_roa_derives_the_address() {
+    let public_key = hex::decode(ED25519_PUBKEY).unwrap();
+    let data =
+        transition::validate_aptos_roa(&stamped(APTOS_ADDRESS.as_bytes()), &public_key).unwrap();
+    let record = view(&data);
+    assert_eq!(
+        record.right_of_association_validation().unwrap(),
+        Validation::Aptos
+    );
+    assert_eq!(
+        record.roa_id,
+        &hex::decode(&APTOS_ADDRESS[2..]).unwrap()[..]
+    );
+
+    let result = transition::validate_aptos_roa(&stamped(SUI_ADDRESS.as_bytes()), &public_key);
+    assert!(matches!(result, Err(SnsRecordsError::AddressMismatch)));
+}
+
+/// A list record of `entries`, its staleness validated by `DOMAIN_OWNER`
+fn stamped_list(entries: &[&[u8]]) -> Vec<u8> {
+    let entries = entries
+        .iter()
+        .map(|e| RecordEntry::new(e.to_vec()))
+        .collect::<Vec<_>>();
+    let content = record_list::serialize_entries(&entries).unwrap();
+    let data = transition::new_record(&content, ContentEncoding::List).unwrap();
+    transition::validate_staleness(&data, Validation::Solana, DOMAIN_OWNER.as_ref()).unwrap()
+}
+
+fn entries(data: &[u8]) -> Vec<RecordEntry> {
+    record_list::parse_entries(view(data).content).unwrap()
+}
+
+#[test]
+fn add_and_remove_entry_reset_the_record_and_keep_the_entry_roas() {
+    let data = stamped_list(&[VERIFIER.as_ref()]);
+    let data = transition::validate_entry_solana_roa(&data, 0, &VERIFIER).unwrap();
+
+    let data = transition::add_entry(&data, b"bonfida").unwrap();
+    let record = view(&data);
+    assert_eq!(record.staleness_validation().unwrap(), Validation::None);
+    assert!(record.staleness_id.is_empty());
+    let list = entries(&data);
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].right_of_association_validation, Validation::Solana);
+    assert_eq!(list[0].roa_id, VERIFIER.as_ref());
+    assert_eq!(list[1], RecordEntry::new(b"bonfida".to_vec()));
+
+    let data = transition::remove_entry(&data, 0).unwrap();
+    assert_eq!(entries(&data), vec![RecordEntry::new(b"bonfida".to_vec())]);
+
+    // Entries are only added to list records
+    let result = transition::add_entry(&stamped(b"bonfida"), b"sol");
+    assert!(matches!(
+        result,
+        Err(SnsRecordsError::InvalidContentEncoding)
+    ));
+}
+
+#[test]
+fn validate_entry_solana_roa_requires_the_entry_key() {
+    let data = stamped_list(&[b"bonfida", VERIFIER.as_ref()]);
+    let result = transition::validate_entry_solana_roa(&data, 0, &VERIFIER);
+    assert!(matches!(result, Err(SnsRecordsError::InvalidVerifier)));
+
+    let data = transition::validate_entry_solana_roa(&data, 1, &VERIFIER).unwrap();
+    let list = entries(&data);
+    assert_eq!(list[0], RecordEntry::new(b"bonfida".to_vec()));
+    assert_eq!(list[1].right_of_association_validation, Validation::Solana);
+    assert_eq!(list[1].roa_id, VERIFIER.as_ref());
+    assert_eq!(view(&data).staleness_id, DOMAIN_OWNER.as_ref());
+}
+
+#[test]
+fn validate_entry_ethereum_roa_recovers_the_entry_address() {
+    let secret_key = libsecp256k1::SecretKey::parse(&[0x11; 32]).unwrap();
+    let other_key = libsecp256k1::SecretKey::parse(&[0x22; 32]).unwrap();
+    let address = common::eth_address(&secret_key);
+    let data = stamped_list(&[b"bonfida", &address]);
+    let message = message_to_sign(&address, &RECORD_KEY, &DOMAIN_OWNER);
+
+    let signature = common::eth_sign(&other_key, &message);
+    let result = transition::validate_entry_ethereum_roa(&data, 1, &RECORD_KEY, &signature);
+    assert!(matches!(result, Err(SnsRecordsError::EthPubkeyMismatch)));
+
+    let signature = common::eth_sign(&secret_key, &message);
+    let data = transition::validate_entry_ethereum_roa(&data, 1, &RECORD_KEY, &signature).unwrap();
+    let list = entries(&data);
+    assert_eq!(
+        list[1].right_of_association_validation,
+        Validation::Ethereum
+    );
+    assert_eq!(list[1].roa_id, address);
+
+    // The message commits to the staleness id of the record
+    let unstamped = transition::add_entry(&data, b"sol").unwrap();
+    let result = transition::validate_entry_ethereum_roa(&unstamped, 1, &RECORD_KEY, &signature);
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+}
+
+#[test]
+fn unverify_entry_requires_the_entry_verifier() {
+    let data = stamped_list(&[VERIFIER.as_ref()]);
+    let data = transition::validate_entry_solana_roa(&data, 0, &VERIFIER).unwrap();
+
+    let result = transition::unverify_entry(&data, 0, &DOMAIN_OWNER);
+    assert!(matches!(result, Err(SnsRecordsError::InvalidVerifier)));
+
+    let data = transition::unverify_entry(&data, 0, &VERIFIER).unwrap();
+    assert_eq!(
+        entries(&data),
+        vec![RecordEntry::new(VERIFIER.to_bytes().to_vec())]
+    );
+    assert_eq!(view(&data).staleness_id, DOMAIN_OWNER.as_ref());
+}
+
+#[test]
+fn entry_instructions_reject_out_of_range_indexes() {
+    let data = stamped_list(&[VERIFIER.as_ref()]);
+    let signature = hex::decode(ETH_SIGNATURE).unwrap();
+    let result = transition::remove_entry(&data, 1);
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+    let result = transition::validate_entry_solana_roa(&data, 1, &VERIFIER);
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+    let result = transition::validate_entry_ethereum_roa(&data, u16::MAX, &RECORD_KEY, &signature);
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+    let result = transition::unverify_entry(&data, 1, &VERIFIER);
+    assert!(matches!(result, Err(SnsRecordsError::OutOfBound)));
+}
+
+#[test]
+fn validate_staleness_resets_the_entry_roas() {
+    let data = stamped_list(&[VERIFIER.as_ref(), b"bonfida"]);
+    let data = transition::validate_entry_solana_roa(&data, 0, &VERIFIER).unwrap();
+    assert_eq!(
+        entries(&data)[0].right_of_association_validation,
+        Validation::Solana
+    );
+
+    let data =
+        transition::validate_staleness(&data, Validation::Solana, VERIFIER.as_ref()).unwrap();
+    assert_eq!(view(&data).staleness_id, VERIFIER.as_ref());
+    assert_eq!(
+        entries(&data),
+        vec![
+            RecordEntry::new(VERIFIER.to_bytes().to_vec()),
+            RecordEntry::new(b"bonfida".to_vec()),
+        ]
+    );
+}